@@ -1,15 +1,31 @@
-use crate::{adapters, cli, config, usecases, validation};
-use std::path::Path;
+use crate::{
+    adapters, answers, cli, config, inspect, renderer, usecases, validation,
+};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use usecases::render_skeleton::{Content, Outcome};
 
 pub fn handle(
     args: cli::Args,
     use_input_terminal: bool,
     use_output_terminal: bool,
 ) -> Result<(), String> {
+    let inputs = collect_inputs(&args);
+
     match (&args, use_input_terminal, use_output_terminal) {
-        (cli::Args { skeleton_config: Some(_), .. }, false, _) => error_action(
-            "Unable to decide between skeleton and standard input.",
-        )?,
+        (cli::Args { command: Some(cli::Command::Update(update)), .. }, ..) => {
+            update_skeleton(
+                update.skeleton_config.as_ref(),
+                &update.output_path,
+                &string_inputs(&update.inputs),
+                update.strict_inputs,
+                use_input_terminal,
+            )?
+        }
+        (
+            cli::Args { command: Some(cli::Command::Inspect(inspect)), .. },
+            ..,
+        ) => inspect_skeleton(&inspect.skeleton_config, inspect.format)?,
         (cli::Args { file_path: Some(_), .. }, false, _) => {
             error_action("Unable to decide between file and standard input.")?
         }
@@ -17,15 +33,52 @@ pub fn handle(
             cli::Args {
                 skeleton_config: Some(skeleton_config),
                 library_dir,
+                diff: true,
                 ..
             },
-            true,
+            _,
+            _,
+        ) => diff_skeleton(
+            library_dir.as_deref(),
+            skeleton_config,
+            &args.output_path,
+            &inputs,
+            args.strict_inputs,
+            use_input_terminal,
+        )?,
+        (
+            cli::Args {
+                skeleton_config: Some(skeleton_config),
+                library_dir,
+                dry_run: true,
+                ..
+            },
+            _,
+            _,
+        ) => dry_run_skeleton(
+            library_dir.as_deref(),
+            skeleton_config,
+            &args.output_path,
+            &inputs,
+            args.strict_inputs,
+            use_input_terminal,
+        )?,
+        (
+            cli::Args {
+                skeleton_config: Some(skeleton_config),
+                library_dir,
+                ..
+            },
+            _,
             true,
         ) => render_skeleton(
             library_dir.as_deref(),
             skeleton_config,
             &args.output_path,
-            &args.inputs,
+            &inputs,
+            args.on_conflict,
+            args.strict_inputs,
+            use_input_terminal,
         )?,
         (
             cli::Args {
@@ -33,20 +86,22 @@ pub fn handle(
                 library_dir,
                 ..
             },
-            true,
+            _,
             false,
         ) => skeleton_to_stdout(
             library_dir.as_deref(),
             skeleton_config,
-            &args.inputs,
+            &inputs,
+            args.strict_inputs,
+            use_input_terminal,
         )?,
         (
             cli::Args { file_path: Some(file_path), library_dir, .. },
             true,
             _,
-        ) => file_to_stdout(library_dir.as_deref(), file_path, &args.inputs)?,
+        ) => file_to_stdout(library_dir.as_deref(), file_path, &inputs)?,
         (cli::Args { skeleton_config: None, library_dir, .. }, ..) => {
-            stdin_to_stdout(library_dir.as_deref(), &args.inputs)?
+            stdin_to_stdout(library_dir.as_deref(), &inputs)?
         }
     }
 
@@ -57,56 +112,403 @@ pub fn render_skeleton(
     library_dir: Option<&Path>,
     config: &config::Config,
     output_path: &Path,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
+    on_conflict: cli::OnConflict,
+    strict_inputs: bool,
+    interactive: bool,
 ) -> Result<(), String> {
-    let cleaned_inputs = clean_inputs(inputs, &config.inputs)?;
+    let cleaned_inputs =
+        clean_inputs(inputs, config, strict_inputs, interactive)?;
 
-    usecases::render_skeleton::execute(
-        adapters::file_finder(&config.template_directory),
+    let files = usecases::render_skeleton::render(
+        skeleton_files(config)?,
+        skeleton_reader(library_dir, config, &cleaned_inputs)?,
+    )
+    .map_err(|error| {
+        eprintln!("{}", error.0);
+        error.to_string()
+    })?;
+
+    adapters::directory_creator(output_path)
+        .or_else(|error| error_action(&error))?;
+    let created_directories = adapters::missing_directories(
+        files.iter().map(|(path, _, _)| path.as_path()),
+        output_path,
+    );
+    let report = usecases::render_skeleton::write_resolving_conflicts(
+        files,
+        |path, content| adapters::file_status(path, content, output_path),
         |path| {
-            adapters::skeleton_file_reader(
-                library_dir,
+            resolve_conflict(path, on_conflict)
+                .map_err(usecases::render_skeleton::Error)
+        },
+        |path, content, mode| {
+            adapters::file_writer(path, &content, mode, output_path)
+                .map_err(usecases::render_skeleton::Error)
+        },
+        |path| {
+            adapters::created_file_remover(
                 path,
-                &cleaned_inputs,
-                &config.template_directory,
+                output_path,
+                &created_directories,
             )
             .map_err(usecases::render_skeleton::Error)
         },
-        |path, content| {
-            adapters::file_writer(path, &content, output_path)
-                .map_err(usecases::render_skeleton::Error)
+    )
+    .map_err(|error| {
+        eprintln!("{}", error.0);
+        error.to_string()
+    })?;
+
+    summarize_conflicts(&report)?;
+    write_answers(config, &cleaned_inputs, output_path)
+}
+
+pub fn dry_run_skeleton(
+    library_dir: Option<&Path>,
+    config: &config::Config,
+    output_path: &Path,
+    inputs: &[(String, toml::Value)],
+    strict_inputs: bool,
+    interactive: bool,
+) -> Result<(), String> {
+    let cleaned_inputs =
+        clean_inputs(inputs, config, strict_inputs, interactive)?;
+
+    let report = usecases::render_skeleton::execute(
+        skeleton_files(config)?,
+        skeleton_reader(library_dir, config, &cleaned_inputs)?,
+        |path, content, _| {
+            Ok(adapters::file_status(path, &content, output_path))
         },
+        |_| Ok(()),
     )
     .map_err(|error| {
         eprintln!("{}", error.0);
         error.to_string()
     })?;
 
+    for (path, outcome) in report {
+        let status = match outcome {
+            Outcome::Created => "create",
+            Outcome::Unchanged => "unchanged",
+            _ => "overwrite",
+        };
+        println!("{status:<9} {}", path.display());
+    }
+
     Ok(())
 }
 
-pub fn skeleton_to_stdout(
+pub fn diff_skeleton(
     library_dir: Option<&Path>,
     config: &config::Config,
-    inputs: &[(String, String)],
+    output_path: &Path,
+    inputs: &[(String, toml::Value)],
+    strict_inputs: bool,
+    interactive: bool,
 ) -> Result<(), String> {
-    let cleaned_inputs = clean_inputs(inputs, &config.inputs)?;
+    let cleaned_inputs =
+        clean_inputs(inputs, config, strict_inputs, interactive)?;
 
     usecases::render_skeleton::execute(
-        adapters::file_finder(&config.template_directory),
+        skeleton_files(config)?,
+        skeleton_reader(library_dir, config, &cleaned_inputs)?,
+        |path, content, _| {
+            adapters::file_differ(path, &content, output_path)
+                .map_err(usecases::render_skeleton::Error)
+        },
+        |_| Ok(()),
+    )
+    .map_err(|error| {
+        eprintln!("{}", error.0);
+        error.to_string()
+    })?;
+
+    Ok(())
+}
+
+fn resolve_conflict(
+    path: &Path,
+    on_conflict: cli::OnConflict,
+) -> Result<Outcome, String> {
+    match on_conflict {
+        cli::OnConflict::Overwrite => Ok(Outcome::Overwritten),
+        cli::OnConflict::Skip => Ok(Outcome::Skipped),
+        cli::OnConflict::Error => Ok(Outcome::Conflicted),
+        cli::OnConflict::Prompt => match adapters::overwrite_prompter(path)? {
+            true => Ok(Outcome::Overwritten),
+            false => Ok(Outcome::Skipped),
+        },
+    }
+}
+
+fn summarize_conflicts(report: &[(PathBuf, Outcome)]) -> Result<(), String> {
+    let skipped = paths_with(report, Outcome::Skipped);
+    let conflicted = paths_with(report, Outcome::Conflicted);
+
+    if !skipped.is_empty() {
+        eprintln!("Skipped existing files:\n{}", skipped.join("\n"));
+    }
+    if !conflicted.is_empty() {
+        error_action(&format!(
+            "Files already exist (use --on-conflict to overwrite or skip them):\n{}",
+            conflicted.join("\n")
+        ))?;
+    }
+
+    Ok(())
+}
+
+fn paths_with(report: &[(PathBuf, Outcome)], outcome: Outcome) -> Vec<String> {
+    report
+        .iter()
+        .filter(|r| r.1 == outcome)
+        .map(|r| format!("    {}", r.0.display()))
+        .collect()
+}
+
+pub fn update_skeleton(
+    skeleton_config: Option<&config::Config>,
+    output_path: &Path,
+    inputs: &[(String, toml::Value)],
+    strict_inputs: bool,
+    interactive: bool,
+) -> Result<(), String> {
+    let answers_path = output_path.join(answers::ANSWERS_NAME);
+    let answers = answers::Answers::from_file(&answers_path).or_else(|_| {
+        error_action(&format!(
+            "Unable to read answers file '{}'.",
+            answers_path.display()
+        ))
+    })?;
+    let Some(revision) = &answers.skeleton.revision else {
+        return error_action(
+            "Unable to update project, the answers file has no skeleton revision.",
+        );
+    };
+
+    let new_config = match skeleton_config {
+        Some(config) => config.to_owned(),
+        None => load_config(&answers.skeleton.path)?,
+    };
+    let checkout =
+        adapters::skeleton_checkout(&answers.skeleton.path, revision)
+            .or_else(|error| error_action(&error))?;
+    let old_config = load_config(checkout.path())?;
+
+    let answered_inputs = answers_inputs(&answers);
+    let old_inputs = clean_inputs(&answered_inputs, &old_config, false, false)?;
+    let new_inputs = clean_inputs(
+        &[answered_inputs, inputs.to_vec()].concat(),
+        &new_config,
+        strict_inputs,
+        interactive,
+    )?;
+
+    let old_files = render_to_memory(&old_config, &old_inputs)?
+        .into_iter()
+        .map(|(path, content, _)| (path, content))
+        .collect();
+    let new_files = render_to_memory(&new_config, &new_inputs)?;
+
+    let report = usecases::update_skeleton::execute(
+        old_files,
+        new_files,
         |path| {
-            adapters::skeleton_file_reader(
-                library_dir,
-                path,
-                &cleaned_inputs,
-                &config.template_directory,
+            adapters::existing_file_reader(path, output_path)
+                .map_err(usecases::update_skeleton::Error)
+        },
+        |path, content, mode| {
+            adapters::file_writer(path, &content, mode, output_path)
+                .map_err(usecases::update_skeleton::Error)
+        },
+        |path| {
+            adapters::file_remover(path, output_path)
+                .map_err(usecases::update_skeleton::Error)
+        },
+    )
+    .map_err(|error| {
+        eprintln!("{}", error.0);
+        error.to_string()
+    })?;
+
+    write_answers(&new_config, &new_inputs, output_path)?;
+
+    let skipped = paths_with(&report, Outcome::Skipped);
+    let conflicted = paths_with(&report, Outcome::Conflicted);
+
+    if !skipped.is_empty() {
+        eprintln!(
+            "Skipped files that were deleted or changed locally:\n{}",
+            skipped.join("\n")
+        );
+    }
+    if !conflicted.is_empty() {
+        error_action(&format!(
+            "Merge conflicts in:\n{}",
+            conflicted.join("\n")
+        ))?;
+    }
+
+    Ok(())
+}
+
+pub fn inspect_skeleton(
+    config: &config::Config,
+    format: cli::InspectFormat,
+) -> Result<(), String> {
+    let mut files: Vec<String> = skeleton_files(config)?
+        .into_iter()
+        .filter_map(|path| {
+            adapters::relative_template_path(
+                &path,
+                &config.template_directories,
             )
-            .map_err(usecases::render_skeleton::Error)
+            .map(|p| match path.is_dir() {
+                true => format!("{}/", p.display()),
+                false => p.display().to_string(),
+            })
+        })
+        .collect();
+    files.sort();
+
+    let output = match format {
+        cli::InspectFormat::Table => inspect::to_table(config, &files),
+        cli::InspectFormat::Json => inspect::to_json(config, &files)
+            .map(|json| format!("{json}\n"))
+            .or_else(|error| error_action(&error))?,
+    };
+    adapters::text_writer(output);
+
+    Ok(())
+}
+
+type SkeletonFiles =
+    Result<Vec<(PathBuf, Content)>, usecases::render_skeleton::Error>;
+
+fn skeleton_reader<'a>(
+    library_dir: Option<&'a Path>,
+    config: &'a config::Config,
+    inputs: &'a [(String, toml::Value)],
+) -> Result<impl Fn(&Path) -> SkeletonFiles + 'a, String> {
+    let copy = adapters::glob_matcher(&config.copy)
+        .or_else(|error| error_action(&error))?;
+    let binary = adapters::glob_matcher(&config.binary)
+        .or_else(|error| error_action(&error))?;
+    let excluded = renderer::excluded_files(inputs, &config.files)
+        .map_err(|error| error.0)
+        .and_then(|globs| adapters::glob_matcher(&globs))
+        .or_else(|error| error_action(&error))?;
+    let repeated = config
+        .files
+        .iter()
+        .filter_map(|f| f.each.as_ref().map(|each| (&f.glob, each)))
+        .map(|(glob, each)| {
+            let matcher = adapters::glob_matcher(&[glob.to_owned()])?;
+            let matches = move |path: &Path| {
+                adapters::relative_template_path(
+                    path,
+                    &config.template_directories,
+                )
+                .is_some_and(|p| matcher.is_match(p))
+            };
+            Ok((matches, each.to_owned()))
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .or_else(|error| error_action(&error))?;
+
+    Ok(move |path: &Path| {
+        let relative_path = adapters::relative_template_path(
+            path,
+            &config.template_directories,
+        );
+        if relative_path.is_some_and(|p| excluded.is_match(p)) {
+            return Ok(Vec::new());
+        }
+
+        usecases::render_skeleton::read_each(
+            path,
+            inputs,
+            &repeated,
+            |inputs| {
+                adapters::skeleton_file_reader(
+                    library_dir,
+                    path,
+                    inputs,
+                    &config.template_directories,
+                    &copy,
+                    &binary,
+                    config.dereference_symlinks,
+                )
+                .map_err(usecases::render_skeleton::Error)
+            },
+        )
+    })
+}
+
+fn skeleton_files(config: &config::Config) -> Result<Vec<PathBuf>, String> {
+    adapters::template_finder(
+        &config.template_directories,
+        &config.ignore,
+        config.dereference_symlinks,
+    )
+    .or_else(|error| error_action(&error))
+}
+
+fn load_config(skeleton_directory: &Path) -> Result<config::Config, String> {
+    let config_path = skeleton_directory.join(config::CONFIG_NAME);
+    config::Config::from_file(&config_path).or_else(|error| {
+        error_action(&format!(
+            "Unable to load config '{}'.\n    {}.",
+            config_path.display(),
+            error
+        ))
+    })
+}
+
+fn render_to_memory(
+    config: &config::Config,
+    inputs: &[(String, toml::Value)],
+) -> Result<Vec<(PathBuf, Content, u32)>, String> {
+    let files = RefCell::new(Vec::new());
+
+    usecases::render_skeleton::execute(
+        skeleton_files(config)?,
+        skeleton_reader(None, config, inputs)?,
+        |path, content, mode| {
+            files.borrow_mut().push((path.to_owned(), content, mode));
+            Ok(Outcome::Created)
         },
-        |_, content| {
-            adapters::text_writer(content);
-            Ok(())
+        |_| Ok(()),
+    )
+    .map_err(|error| {
+        eprintln!("{}", error.0);
+        error.to_string()
+    })?;
+
+    Ok(files.into_inner())
+}
+
+pub fn skeleton_to_stdout(
+    library_dir: Option<&Path>,
+    config: &config::Config,
+    inputs: &[(String, toml::Value)],
+    strict_inputs: bool,
+    interactive: bool,
+) -> Result<(), String> {
+    let cleaned_inputs =
+        clean_inputs(inputs, config, strict_inputs, interactive)?;
+
+    usecases::render_skeleton::execute(
+        skeleton_files(config)?,
+        skeleton_reader(library_dir, config, &cleaned_inputs)?,
+        |_, content, _| {
+            adapters::bytes_writer(content.as_bytes())
+                .map_err(usecases::render_skeleton::Error)?;
+            Ok(Outcome::Created)
         },
+        |_| Ok(()),
     )
     .map_err(|error| {
         eprintln!("{}", error.0);
@@ -119,7 +521,7 @@ pub fn skeleton_to_stdout(
 pub fn file_to_stdout(
     library_dir: Option<&Path>,
     path: &Path,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<(), String> {
     usecases::render_text::execute(
         || {
@@ -138,14 +540,14 @@ pub fn file_to_stdout(
     Ok(())
 }
 
-pub fn error_action(message: &str) -> Result<(), String> {
+pub fn error_action<T>(message: &str) -> Result<T, String> {
     eprintln!("{message}");
     Err(message)?
 }
 
 pub fn stdin_to_stdout(
     library_dir: Option<&Path>,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<(), String> {
     usecases::render_text::execute(
         || {
@@ -165,31 +567,207 @@ pub fn stdin_to_stdout(
     Ok(())
 }
 
+fn write_answers(
+    config: &config::Config,
+    inputs: &[(String, toml::Value)],
+    output_path: &Path,
+) -> Result<(), String> {
+    let answers = answers::Answers {
+        skeleton: answers::Skeleton {
+            name: config.skeleton.name.to_owned(),
+            version: config.skeleton.version.to_owned(),
+            path: config.skeleton_directory.to_owned(),
+            revision: adapters::skeleton_revision(&config.skeleton_directory),
+        },
+        inputs: inputs
+            .iter()
+            .filter(|i| config.inputs.iter().any(|ci| ci.name == i.0))
+            .cloned()
+            .collect(),
+    };
+
+    let path = output_path.join(answers::ANSWERS_NAME);
+    answers.to_file(&path).or_else(|_| {
+        error_action(&format!(
+            "Unable to write answers file '{}'.",
+            path.display()
+        ))
+    })
+}
+
+fn collect_inputs(args: &cli::Args) -> Vec<(String, toml::Value)> {
+    let answered_inputs =
+        args.answers.iter().flat_map(answers_inputs).collect::<Vec<_>>();
+    let file_inputs = args.inputs_file.iter().flatten();
+
+    answered_inputs
+        .into_iter()
+        .chain(file_inputs.map(|(k, v)| (k.to_owned(), v.to_owned())))
+        .chain(string_inputs(&args.inputs))
+        .collect()
+}
+
+fn answers_inputs(answers: &answers::Answers) -> Vec<(String, toml::Value)> {
+    answers.inputs.iter().map(|(k, v)| (k.to_owned(), v.to_owned())).collect()
+}
+
+fn string_inputs(inputs: &[(String, String)]) -> Vec<(String, toml::Value)> {
+    inputs
+        .iter()
+        .map(|i| (i.0.to_owned(), toml::Value::String(i.1.to_owned())))
+        .collect()
+}
+
 fn clean_inputs(
-    user_inputs: &[(String, String)],
-    config_inputs: &[config::Input],
-) -> Result<Vec<(String, String)>, String> {
-    let inputs = validation::validate_inputs(user_inputs, config_inputs)
-        .map_err(|error| {
-            let errors = error.0.iter().fold(String::new(), |acc, e| {
-                let msg = match e {
-                    validation::ErrorType::MissingInput(name) => {
-                        format!("Missing input '{}'.", name)
-                    }
-                    validation::ErrorType::InvalidOption(key, value, options) => {
-                        format!(
-                            "Invalid option '{}' to input '{}'. Available options: {}.",
-                            value, key, options.join(", ")
-                        )
-                    }
-                };
-                format!("{}{}\n", acc, msg)
-            });
-
-            eprint!("{errors}");
-
-            errors
+    user_inputs: &[(String, toml::Value)],
+    config: &config::Config,
+    strict_inputs: bool,
+    interactive: bool,
+) -> Result<Vec<(String, toml::Value)>, String> {
+    let mut user_inputs = user_inputs.to_vec();
+    if interactive {
+        prompt_missing_inputs(&mut user_inputs, &config.inputs)?;
+    }
+
+    let mut errors = Vec::new();
+    let unknown_inputs =
+        validation::find_unknown_inputs(&user_inputs, &config.inputs);
+    if strict_inputs {
+        errors.extend(unknown_inputs);
+    } else {
+        for warning in unknown_inputs {
+            eprintln!("Warning: {}", validation_message(&warning));
+        }
+    }
+
+    let inputs = validation::validate_inputs(&user_inputs, &config.inputs)
+        .unwrap_or_else(|error| {
+            errors.extend(error.0);
+            Vec::new()
+        });
+
+    if !errors.is_empty() {
+        let errors = errors.iter().fold(String::new(), |acc, e| {
+            format!("{}{}\n", acc, validation_message(e))
+        });
+
+        eprint!("{errors}");
+
+        return Err(errors);
+    }
+
+    let inputs =
+        renderer::compute(&inputs, &config.computed).map_err(|error| {
+            eprintln!("{}", error.0);
+            error.0
         })?;
 
     Ok(inputs)
 }
+
+fn validation_message(error: &validation::ErrorType) -> String {
+    match error {
+        validation::ErrorType::MissingInput(name) => {
+            format!("Missing input '{}'.", name)
+        }
+        validation::ErrorType::UnknownInput(name, suggestion) => {
+            match suggestion {
+                Some(suggestion) => format!(
+                    "Unknown input '{}'. Did you mean '{}'?",
+                    name, suggestion
+                ),
+                None => format!("Unknown input '{}'.", name),
+            }
+        }
+        validation::ErrorType::InvalidOption(key, value, options) => {
+            format!(
+                "Invalid option '{}' to input '{}'. Available options: {}.",
+                value,
+                key,
+                options.join(", ")
+            )
+        }
+        validation::ErrorType::InvalidType(key, value, kind) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Expected a value of type {}.",
+                value, key, kind
+            )
+        }
+        validation::ErrorType::PatternMismatch(key, value, pattern) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Must match pattern '{}'.",
+                value, key, pattern
+            )
+        }
+        validation::ErrorType::TooShort(key, value, min_length) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Must have at least {} characters or items.",
+                value, key, min_length
+            )
+        }
+        validation::ErrorType::TooLong(key, value, max_length) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Must have at most {} characters or items.",
+                value, key, max_length
+            )
+        }
+        validation::ErrorType::TooSmall(key, value, min) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Must be at least {}.",
+                value, key, min
+            )
+        }
+        validation::ErrorType::TooLarge(key, value, max) => {
+            format!(
+                "Invalid value '{}' to input '{}'. Must be at most {}.",
+                value, key, max
+            )
+        }
+        validation::ErrorType::InvalidCondition(key, message) => {
+            format!(
+                "Unable to evaluate condition of input '{}'.\n    {}",
+                key, message
+            )
+        }
+    }
+}
+
+fn prompt_missing_inputs(
+    user_inputs: &mut Vec<(String, toml::Value)>,
+    config_inputs: &[config::Input],
+) -> Result<(), String> {
+    while let Some(input) = next_missing_input(user_inputs, config_inputs) {
+        let value = adapters::input_prompter(input).map_err(|error| {
+            eprintln!("{error}");
+            error
+        })?;
+        user_inputs.push((input.name.to_owned(), toml::Value::String(value)));
+
+        let errors =
+            validation::check_input(&input.name, user_inputs, config_inputs);
+        if !errors.is_empty() {
+            for error in &errors {
+                eprintln!("{}", validation_message(error));
+            }
+            user_inputs.pop();
+        }
+    }
+
+    Ok(())
+}
+
+fn next_missing_input<'a>(
+    user_inputs: &[(String, toml::Value)],
+    config_inputs: &'a [config::Input],
+) -> Option<&'a config::Input> {
+    let errors = validation::validate_inputs(user_inputs, config_inputs)
+        .err()
+        .map(|error| error.0)
+        .unwrap_or_default();
+
+    config_inputs.iter().find(|input| {
+        let missing = validation::ErrorType::MissingInput(input.name.clone());
+        errors.contains(&missing)
+            && !user_inputs.iter().any(|ui| ui.0 == input.name)
+    })
+}
@@ -1,44 +1,149 @@
-use crate::renderer;
+use crate::usecases::render_skeleton::{Content, Outcome};
+use crate::{config, prompt, renderer};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::Read;
+use std::io::{ErrorKind, Read, Write};
+use std::os::unix::fs::{PermissionsExt, symlink};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 fn make_error_message(message: &str, error: &str) -> String {
     format!("{}\n    {}", message, error)
 }
 
-pub fn file_finder(path: &Path) -> impl IntoIterator<Item = PathBuf> + use<> {
+/// Directories are only listed when they are empty, so they can be recreated.
+/// Symbolic links are listed as they are, unless they are dereferenced.
+pub fn file_finder(
+    path: &Path,
+    ignore: &Gitignore,
+    dereference_symlinks: bool,
+) -> Vec<PathBuf> {
+    let ignore_file = path.join(config::IGNORE_NAME);
     walkdir::WalkDir::new(path)
         .min_depth(1)
+        .follow_links(dereference_symlinks)
         .into_iter()
+        .filter_entry(|e| {
+            let is_dir = e.file_type().is_dir();
+            !ignore.matched_path_or_any_parents(e.path(), is_dir).is_ignore()
+        })
         .filter_map(|e| e.ok())
+        .filter(|e| {
+            !e.file_type().is_dir() || is_empty_directory(e.path(), ignore)
+        })
         .map(|e| e.path().to_owned())
-        .filter(|p| !p.is_dir())
+        .filter(|p| *p != ignore_file)
+        .collect()
 }
 
-pub fn skeleton_file_reader(
-    template_dir: Option<&Path>,
-    path: &Path,
-    inputs: &[(String, String)],
-    template_directory: &Path,
-) -> Result<(PathBuf, String), String> {
-    let rendered_template = render_template(template_dir, path, inputs)
-        .map_err(|e| {
+/// A directory counts as empty when every entry in it is ignored, so a
+/// placeholder directory holding only editor or OS clutter is still created.
+fn is_empty_directory(path: &Path, ignore: &Gitignore) -> bool {
+    fs::read_dir(path).is_ok_and(|mut entries| {
+        entries.all(|entry| {
+            entry.is_ok_and(|e| {
+                let path = e.path();
+                ignore
+                    .matched_path_or_any_parents(&path, path.is_dir())
+                    .is_ignore()
+            })
+        })
+    })
+}
+
+pub fn template_finder(
+    template_directories: &[PathBuf],
+    ignore_patterns: &[String],
+    dereference_symlinks: bool,
+) -> Result<Vec<PathBuf>, String> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut indexes: HashMap<PathBuf, usize> = HashMap::new();
+    for directory in template_directories {
+        let ignore = ignore_matcher(directory, ignore_patterns)?;
+        for path in file_finder(directory, &ignore, dereference_symlinks) {
+            let relative_path =
+                path.strip_prefix(directory).unwrap_or(&path).to_owned();
+            match indexes.get(&relative_path) {
+                Some(&index) => files[index] = path,
+                None => {
+                    indexes.insert(relative_path, files.len());
+                    files.push(path);
+                }
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn ignore_matcher(
+    directory: &Path,
+    patterns: &[String],
+) -> Result<Gitignore, String> {
+    let mut builder = GitignoreBuilder::new(directory);
+    for pattern in patterns {
+        builder.add_line(None, pattern).map_err(|e| {
             make_error_message(
-                &format!("Unable to render file '{}'.", path.display()),
-                &e,
+                &format!("Unable to parse ignore pattern '{pattern}'."),
+                &e.to_string(),
             )
         })?;
-    let relative_path = path.strip_prefix(template_directory).map_err(|e| {
-        make_error_message(
-            &format!(
-                "Unable to strip template path '{}' from path '{}'.",
-                template_directory.display(),
-                path.display()
-            ),
+    }
+
+    let ignore_file = directory.join(config::IGNORE_NAME);
+    if ignore_file.is_file()
+        && let Some(e) = builder.add(&ignore_file)
+    {
+        return Err(make_error_message(
+            &format!("Unable to parse '{}'.", ignore_file.display()),
             &e.to_string(),
-        )
-    })?;
+        ));
+    }
+
+    builder.build().map_err(|e| {
+        make_error_message("Unable to build ignore patterns.", &e.to_string())
+    })
+}
+
+pub fn relative_template_path<'a>(
+    path: &'a Path,
+    template_directories: &[PathBuf],
+) -> Option<&'a Path> {
+    template_directories.iter().find_map(|d| path.strip_prefix(d).ok())
+}
+
+pub fn glob_matcher(patterns: &[String]) -> Result<GlobSet, String> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| {
+            make_error_message(
+                &format!("Unable to parse glob '{pattern}'."),
+                &e.to_string(),
+            )
+        })?;
+        builder.add(glob);
+    }
+
+    builder.build().map_err(|e| {
+        make_error_message("Unable to build globs.", &e.to_string())
+    })
+}
+
+pub fn skeleton_file_reader(
+    template_dir: Option<&Path>,
+    path: &Path,
+    inputs: &[(String, toml::Value)],
+    template_directories: &[PathBuf],
+    copy: &GlobSet,
+    binary: &GlobSet,
+    dereference_symlinks: bool,
+) -> Result<Option<(PathBuf, Content)>, String> {
+    let relative_path = relative_template_path(path, template_directories)
+        .ok_or(format!(
+            "Path '{}' is outside of the template directories.",
+            path.display()
+        ))?;
     let rendered_relative_path =
         render_path(relative_path, inputs).map_err(|e| {
             make_error_message(
@@ -46,37 +151,105 @@ pub fn skeleton_file_reader(
                 &e,
             )
         })?;
+    let Some(rendered_relative_path) = rendered_relative_path else {
+        return Ok(None);
+    };
+    if !dereference_symlinks && path.is_symlink() {
+        let target = render_link_target(path, inputs).map_err(|e| {
+            make_error_message(
+                &format!("Unable to render link '{}'.", path.display()),
+                &e,
+            )
+        })?;
+        return Ok(Some((rendered_relative_path, Content::Symlink(target))));
+    }
+    if path.is_dir() {
+        return Ok(Some((rendered_relative_path, Content::Directory)));
+    }
 
-    Ok((rendered_relative_path, rendered_template))
+    let bytes = fs::read(path).map_err(|e| {
+        make_error_message(
+            &format!("Unable to read file '{}'.", path.display()),
+            &e.to_string(),
+        )
+    })?;
+    let content = match Content::from(bytes) {
+        Content::Text(text) if binary.is_match(relative_path) => {
+            Content::Binary(text.into_bytes())
+        }
+        Content::Text(text) if !copy.is_match(relative_path) => {
+            let rendered_text = render_content(template_dir, &text, inputs)
+                .map_err(|e| {
+                    make_error_message(
+                        &format!("Unable to render file '{}'.", path.display()),
+                        &e,
+                    )
+                })?;
+            Content::Text(rendered_text)
+        }
+        content => content,
+    };
+
+    Ok(Some((rendered_relative_path, content)))
 }
 
 fn render_template(
     template_dir: Option<&Path>,
     path: &Path,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<String, String> {
-    let template_dir_str = template_dir.map(|p| p.to_str().unwrap_or_default());
     let content = fs::read_to_string(path).map_err(|e| {
         make_error_message("Unable to read template.", &e.to_string())
     })?;
-    let rendered_content = renderer::render(template_dir_str, &content, inputs)
+    render_content(template_dir, &content, inputs)
+}
+
+fn render_content(
+    template_dir: Option<&Path>,
+    content: &str,
+    inputs: &[(String, toml::Value)],
+) -> Result<String, String> {
+    let template_dir_str = template_dir.map(|p| p.to_str().unwrap_or_default());
+    let rendered_content = renderer::render(template_dir_str, content, inputs)
         .map_err(|e| e.0.to_string())?;
     Ok(rendered_content)
 }
 
+/// Paths that render to an empty string, or with an empty segment, are not
+/// generated, so `{% if use_docker %}docker{% endif %}/` skips a directory.
 fn render_path(
     path: &Path,
-    inputs: &[(String, String)],
-) -> Result<PathBuf, String> {
+    inputs: &[(String, toml::Value)],
+) -> Result<Option<PathBuf>, String> {
     let raw_path = path.to_str().ok_or("Unable to convert path to string.")?;
     let rendered_path = renderer::render(None, raw_path, inputs)
         .map_err(|e| make_error_message("Unable to render path.", &e.0))?;
-    Ok(PathBuf::from(rendered_path))
+    if rendered_path.split(std::path::MAIN_SEPARATOR).any(str::is_empty) {
+        return Ok(None);
+    }
+
+    Ok(Some(PathBuf::from(rendered_path)))
+}
+
+fn render_link_target(
+    path: &Path,
+    inputs: &[(String, toml::Value)],
+) -> Result<PathBuf, String> {
+    let target = fs::read_link(path).map_err(|e| {
+        make_error_message("Unable to read link.", &e.to_string())
+    })?;
+    let raw_target =
+        target.to_str().ok_or("Unable to convert link target to string.")?;
+    let rendered_target =
+        renderer::render(None, raw_target, inputs).map_err(|e| {
+            make_error_message("Unable to render link target.", &e.0)
+        })?;
+    Ok(PathBuf::from(rendered_target))
 }
 
 pub fn text_reader(
     template_dir: Option<&Path>,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<String, String> {
     let template_dir_str = template_dir.map(|p| p.to_str().unwrap_or_default());
     let mut content = String::new();
@@ -88,14 +261,107 @@ pub fn text_reader(
     Ok(rendered_content)
 }
 
+pub fn skeleton_revision(skeleton_directory: &Path) -> Option<String> {
+    git(skeleton_directory, &["rev-parse", "HEAD"])
+        .ok()
+        .filter(|r| !r.is_empty())
+}
+
+pub fn skeleton_checkout(
+    skeleton_directory: &Path,
+    revision: &str,
+) -> Result<tempfile::TempDir, String> {
+    let top_level = git(skeleton_directory, &["rev-parse", "--show-toplevel"])?;
+    let prefix = git(skeleton_directory, &["rev-parse", "--show-prefix"])?;
+    let checkout = tempfile::tempdir().map_err(|e| {
+        make_error_message(
+            "Unable to create temporary directory.",
+            &e.to_string(),
+        )
+    })?;
+
+    let mut archive = Command::new("git")
+        .arg("-C")
+        .arg(top_level)
+        .args(["archive", "--format=tar", &format!("{revision}:{prefix}")])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            make_error_message("Unable to run git.", &e.to_string())
+        })?;
+    let archive_output = archive.stdout.take().ok_or("Unable to run git.")?;
+    let extracted = Command::new("tar")
+        .arg("-x")
+        .arg("-C")
+        .arg(checkout.path())
+        .stdin(archive_output)
+        .status()
+        .map_err(|e| {
+            make_error_message("Unable to run tar.", &e.to_string())
+        })?;
+    let archived = archive.wait_with_output().map_err(|e| {
+        make_error_message("Unable to run git.", &e.to_string())
+    })?;
+
+    if !archived.status.success() || !extracted.success() {
+        return Err(make_error_message(
+            &format!(
+                "Unable to check out revision '{}' of skeleton '{}'.",
+                revision,
+                skeleton_directory.display()
+            ),
+            String::from_utf8_lossy(&archived.stderr).trim(),
+        ));
+    }
+
+    Ok(checkout)
+}
+
+fn git(directory: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| {
+            make_error_message("Unable to run git.", &e.to_string())
+        })?;
+    if !output.status.success() {
+        return Err(make_error_message(
+            &format!("Unable to run git in '{}'.", directory.display()),
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
 pub fn text_writer(content: String) {
     print!("{content}");
 }
 
+pub fn bytes_writer(content: &[u8]) -> Result<(), String> {
+    std::io::stdout().write_all(content).map_err(|e| {
+        make_error_message("Unable to write to stdout.", &e.to_string())
+    })
+}
+
+pub fn input_prompter(input: &config::Input) -> Result<String, String> {
+    prompt::ask(input, &mut std::io::stdin().lock(), &mut std::io::stderr())
+        .map_err(|e| {
+            make_error_message(
+                &format!("Unable to read input '{}'.", input.name),
+                &e.0,
+            )
+        })
+}
+
 pub fn file_reader(
     template_dir: Option<&Path>,
     path: &Path,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<String, String> {
     let rendered_template = render_template(template_dir, path, inputs)
         .map_err(|e| {
@@ -110,10 +376,35 @@ pub fn file_reader(
 
 pub fn file_writer(
     path: &Path,
-    content: &str,
+    content: &Content,
+    mode: u32,
     output_path: &Path,
 ) -> Result<(), String> {
     let output_path = output_path.join(path);
+    let replaceable = match content {
+        Content::Directory | Content::Symlink(_) => {
+            fs::symlink_metadata(&output_path).is_ok_and(|m| !m.is_dir())
+        }
+        _ => output_path.is_symlink(),
+    };
+    if replaceable {
+        fs::remove_file(&output_path).map_err(|e| {
+            make_error_message(
+                &format!("Unable to replace path '{}'.", output_path.display()),
+                &e.to_string(),
+            )
+        })?;
+    }
+
+    if *content == Content::Directory {
+        return fs::create_dir_all(&output_path).map_err(|e| {
+            make_error_message(
+                &format!("Unable to create path '{}'.", output_path.display()),
+                &e.to_string(),
+            )
+        });
+    }
+
     let output_directory = output_path.parent().ok_or(format!(
         "Unable to fetch parent directory of '{}'.",
         path.display()
@@ -124,7 +415,17 @@ pub fn file_writer(
             &e.to_string(),
         )
     })?;
-    fs::write(&output_path, content).map_err(|e| {
+
+    if let Content::Symlink(target) = content {
+        return symlink(target, &output_path).map_err(|e| {
+            make_error_message(
+                &format!("Unable to create link '{}'.", output_path.display()),
+                &e.to_string(),
+            )
+        });
+    }
+
+    fs::write(&output_path, content.as_bytes()).map_err(|e| {
         make_error_message(
             &format!(
                 "Unable to write content to path '{}'.",
@@ -133,5 +434,598 @@ pub fn file_writer(
             &e.to_string(),
         )
     })?;
+    fs::set_permissions(&output_path, fs::Permissions::from_mode(mode))
+        .map_err(|e| {
+            make_error_message(
+                &format!(
+                    "Unable to set permissions of path '{}'.",
+                    &output_path.display()
+                ),
+                &e.to_string(),
+            )
+        })?;
+    Ok(())
+}
+
+pub fn existing_file_reader(
+    path: &Path,
+    output_path: &Path,
+) -> Result<Option<Content>, String> {
+    let file_path = output_path.join(path);
+    if file_path.is_symlink() {
+        let target = fs::read_link(&file_path).map_err(|e| {
+            make_error_message(
+                &format!("Unable to read link '{}'.", file_path.display()),
+                &e.to_string(),
+            )
+        })?;
+        return Ok(Some(Content::Symlink(target)));
+    }
+    if !file_path.exists() {
+        return Ok(None);
+    }
+    if file_path.is_dir() {
+        return Ok(Some(Content::Directory));
+    }
+
+    let content = fs::read(&file_path).map_err(|e| {
+        make_error_message(
+            &format!("Unable to read file '{}'.", file_path.display()),
+            &e.to_string(),
+        )
+    })?;
+    Ok(Some(Content::from(content)))
+}
+
+pub fn directory_creator(path: &Path) -> Result<(), String> {
+    fs::create_dir_all(path).map_err(|e| {
+        make_error_message(
+            &format!("Unable to create directory '{}'.", path.display()),
+            &e.to_string(),
+        )
+    })
+}
+
+pub fn file_remover(path: &Path, output_path: &Path) -> Result<(), String> {
+    remove_path(path, output_path, |_| true)
+}
+
+/// Removes a file written by this run, along with the directories the run
+/// created for it, leaving directories that existed before alone.
+pub fn created_file_remover(
+    path: &Path,
+    output_path: &Path,
+    created_directories: &HashSet<PathBuf>,
+) -> Result<(), String> {
+    remove_path(path, output_path, |dir| created_directories.contains(dir))
+}
+
+pub fn missing_directories<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    output_path: &Path,
+) -> HashSet<PathBuf> {
+    paths
+        .into_iter()
+        .flat_map(|path| {
+            path.ancestors()
+                .skip(1)
+                .filter(|a| !a.as_os_str().is_empty())
+                .map(|a| output_path.join(a))
+                .filter(|a| !a.exists())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn remove_path<P>(
+    path: &Path,
+    output_path: &Path,
+    removable: P,
+) -> Result<(), String>
+where
+    P: Fn(&Path) -> bool,
+{
+    let file_path = output_path.join(path);
+    let removed = match file_path.is_dir() && !file_path.is_symlink() {
+        true => fs::remove_dir(&file_path).or_else(|e| match e.kind() {
+            ErrorKind::DirectoryNotEmpty => Ok(()),
+            _ => Err(e),
+        }),
+        false => fs::remove_file(&file_path),
+    };
+    removed.map_err(|e| {
+        make_error_message(
+            &format!("Unable to remove file '{}'.", file_path.display()),
+            &e.to_string(),
+        )
+    })?;
+
+    let mut directory = file_path.parent();
+    while let Some(dir) = directory.filter(|d| *d != output_path) {
+        if !removable(dir) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        directory = dir.parent();
+    }
+
     Ok(())
 }
+
+pub fn file_status(
+    path: &Path,
+    content: &Content,
+    output_path: &Path,
+) -> Outcome {
+    let output_path = output_path.join(path);
+    match content {
+        Content::Directory => {
+            return match output_path.exists() {
+                true if output_path.is_dir() => Outcome::Unchanged,
+                true => Outcome::Overwritten,
+                false => Outcome::Created,
+            };
+        }
+        Content::Symlink(target) => {
+            return match fs::read_link(&output_path) {
+                Ok(current) if current == *target => Outcome::Unchanged,
+                Ok(_) => Outcome::Overwritten,
+                Err(_) if output_path.exists() => Outcome::Overwritten,
+                Err(_) => Outcome::Created,
+            };
+        }
+        _ => {}
+    }
+
+    match fs::read(&output_path) {
+        Ok(current) if current == content.as_bytes() => Outcome::Unchanged,
+        Ok(_) => Outcome::Overwritten,
+        Err(_) if output_path.exists() => Outcome::Overwritten,
+        Err(_) => Outcome::Created,
+    }
+}
+
+pub fn file_differ(
+    path: &Path,
+    content: &Content,
+    output_path: &Path,
+) -> Result<Outcome, String> {
+    write_diff(path, content, output_path, &mut std::io::stdout())
+}
+
+fn write_diff(
+    path: &Path,
+    content: &Content,
+    output_path: &Path,
+    writer: &mut impl Write,
+) -> Result<Outcome, String> {
+    let write_error = |e: std::io::Error| {
+        make_error_message("Unable to write diff.", &e.to_string())
+    };
+
+    match content {
+        Content::Directory => {
+            return Ok(file_status(path, content, output_path));
+        }
+        Content::Symlink(target) => {
+            let outcome = file_status(path, content, output_path);
+            if outcome != Outcome::Unchanged {
+                writeln!(
+                    writer,
+                    "Link b/{} -> {}",
+                    path.display(),
+                    target.display()
+                )
+                .map_err(write_error)?;
+            }
+            return Ok(outcome);
+        }
+        _ => {}
+    }
+
+    let output_path = output_path.join(path);
+    let (original_name, original) = match fs::read(&output_path) {
+        Ok(current) => (format!("a/{}", path.display()), current),
+        Err(_) if !output_path.exists() => ("/dev/null".to_owned(), Vec::new()),
+        Err(e) => Err(make_error_message(
+            &format!("Unable to read file '{}'.", output_path.display()),
+            &e.to_string(),
+        ))?,
+    };
+    let modified_name = format!("b/{}", path.display());
+
+    if original == content.as_bytes() {
+        return Ok(Outcome::Unchanged);
+    }
+
+    match (Content::from(original), content) {
+        (Content::Text(original), Content::Text(content)) => {
+            let patch = diffy::DiffOptions::new()
+                .set_original_filename(original_name)
+                .set_modified_filename(modified_name)
+                .create_patch(&original, content)
+                .to_string();
+            write!(writer, "{patch}").map_err(write_error)?;
+        }
+        _ => {
+            writeln!(
+                writer,
+                "Binary files {original_name} and {modified_name} differ"
+            )
+            .map_err(write_error)?;
+        }
+    }
+
+    match output_path.exists() {
+        true => Ok(Outcome::Overwritten),
+        false => Ok(Outcome::Created),
+    }
+}
+
+pub fn overwrite_prompter(path: &Path) -> Result<bool, String> {
+    prompt::confirm(
+        &format!("File '{}' already exists. Overwrite?", path.display()),
+        &mut std::io::stdin().lock(),
+        &mut std::io::stderr(),
+    )
+    .map_err(|e| make_error_message("Unable to read answer.", &e.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_only_directories_created_by_the_run() {
+        let output = tempfile::tempdir().unwrap();
+        fs::create_dir(output.path().join("logs")).unwrap();
+        let paths = [Path::new("logs/app/app.log"), Path::new("new/file")];
+        let created_directories =
+            missing_directories(paths.iter().copied(), output.path());
+        for path in paths {
+            let content = Content::Text(String::new());
+            file_writer(path, &content, 0o644, output.path()).unwrap();
+        }
+
+        for path in paths.iter().rev() {
+            created_file_remover(path, output.path(), &created_directories)
+                .unwrap();
+        }
+
+        assert!(output.path().join("logs").is_dir());
+        assert!(!output.path().join("logs/app").exists());
+        assert!(!output.path().join("new").exists());
+    }
+
+    #[test]
+    fn find_empty_directories_ignoring_ignored_entries() {
+        let template = tempfile::tempdir().unwrap();
+        fs::create_dir(template.path().join("logs")).unwrap();
+        fs::create_dir(template.path().join("vim")).unwrap();
+        fs::write(template.path().join("vim/.swap.swp"), "").unwrap();
+        fs::create_dir(template.path().join("src")).unwrap();
+        fs::write(template.path().join("src/main.rs"), "").unwrap();
+        let mut builder = GitignoreBuilder::new(template.path());
+        builder.add_line(None, "**/*.swp").unwrap();
+        let ignore = builder.build().unwrap();
+
+        let result = file_finder(template.path(), &ignore, false);
+
+        assert_eq!(
+            relative_paths(template.path(), result),
+            vec!["logs", "src/main.rs", "vim"]
+        );
+    }
+
+    #[test]
+    fn create_empty_directory() {
+        let output = tempfile::tempdir().unwrap();
+
+        file_writer(Path::new("logs"), &Content::Directory, 0, output.path())
+            .unwrap();
+
+        assert!(output.path().join("logs").is_dir());
+    }
+
+    fn write(directory: &Path, path: &str, content: &str) {
+        let path = directory.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn relative_paths(directory: &Path, paths: Vec<PathBuf>) -> Vec<String> {
+        let mut result: Vec<String> = paths
+            .iter()
+            .map(|p| p.strip_prefix(directory).unwrap().display().to_string())
+            .collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn skip_ignored_files() {
+        let template = tempfile::tempdir().unwrap();
+        write(template.path(), "src/main.rs", "");
+        write(template.path(), "src/.main.rs.swp", "");
+        write(template.path(), ".git/HEAD", "");
+        write(template.path(), "notes.txt", "");
+        write(template.path(), config::IGNORE_NAME, "*.txt\n");
+
+        let result = template_finder(
+            &[template.path().to_owned()],
+            &["**/*.swp".to_owned(), ".git/".to_owned()],
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            relative_paths(template.path(), result),
+            vec!["src/main.rs"]
+        );
+    }
+
+    fn read(
+        template: &Path,
+        path: &str,
+        inputs: &[(String, toml::Value)],
+        copy: &[&str],
+        dereference_symlinks: bool,
+    ) -> Option<(PathBuf, Content)> {
+        let copy: Vec<String> = copy.iter().map(|c| c.to_string()).collect();
+        skeleton_file_reader(
+            None,
+            &template.join(path),
+            inputs,
+            &[template.to_owned()],
+            &glob_matcher(&copy).unwrap(),
+            &glob_matcher(&[]).unwrap(),
+            dereference_symlinks,
+        )
+        .unwrap()
+    }
+
+    fn name_input(value: &str) -> Vec<(String, toml::Value)> {
+        vec![("name".to_owned(), toml::Value::String(value.to_owned()))]
+    }
+
+    #[test]
+    fn copy_content_verbatim_but_render_path() {
+        let template = tempfile::tempdir().unwrap();
+        write(template.path(), "{{ name }}.j2", "{{ value }} {% raw %}\n");
+
+        let result = read(
+            template.path(),
+            "{{ name }}.j2",
+            &name_input("config"),
+            &["**/*.j2"],
+            false,
+        );
+
+        assert_eq!(
+            result,
+            Some((
+                PathBuf::from("config.j2"),
+                Content::Text("{{ value }} {% raw %}\n".to_owned())
+            ))
+        );
+    }
+
+    #[test]
+    fn skip_path_rendering_to_empty_string() {
+        let inputs = [("use_docker".to_owned(), toml::Value::Boolean(false))];
+
+        let result = render_path(
+            Path::new("{% if use_docker %}Dockerfile{% endif %}"),
+            &inputs,
+        );
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn skip_path_with_empty_segment() {
+        let inputs = [("use_docker".to_owned(), toml::Value::Boolean(false))];
+
+        let result = render_path(
+            Path::new("{% if use_docker %}docker{% endif %}/Dockerfile"),
+            &inputs,
+        );
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn keep_non_empty_path() {
+        let inputs = [("use_docker".to_owned(), toml::Value::Boolean(true))];
+
+        let result = render_path(
+            Path::new("{% if use_docker %}docker{% endif %}/Dockerfile"),
+            &inputs,
+        );
+
+        assert_eq!(result, Ok(Some(PathBuf::from("docker/Dockerfile"))));
+    }
+
+    #[test]
+    fn reproduce_symlink_with_rendered_target() {
+        let template = tempfile::tempdir().unwrap();
+        write(template.path(), "docs/index.md", "");
+        symlink("docs/{{ name }}.md", template.path().join("README.md"))
+            .unwrap();
+
+        let result = read(
+            template.path(),
+            "README.md",
+            &name_input("index"),
+            &[],
+            false,
+        );
+
+        assert_eq!(
+            result,
+            Some((
+                PathBuf::from("README.md"),
+                Content::Symlink(PathBuf::from("docs/index.md"))
+            ))
+        );
+    }
+
+    #[test]
+    fn read_through_dereferenced_symlink() {
+        let template = tempfile::tempdir().unwrap();
+        write(template.path(), "docs/index.md", "Hello {{ name }}");
+        symlink("docs/index.md", template.path().join("README.md")).unwrap();
+        fs::create_dir(template.path().join("real")).unwrap();
+        write(template.path(), "real/file", "");
+        symlink("real", template.path().join("alias")).unwrap();
+
+        let result =
+            read(template.path(), "README.md", &name_input("World"), &[], true);
+        let files =
+            template_finder(&[template.path().to_owned()], &[], true).unwrap();
+
+        assert_eq!(
+            result,
+            Some((
+                PathBuf::from("README.md"),
+                Content::Text("Hello World".to_owned())
+            ))
+        );
+        assert_eq!(
+            relative_paths(template.path(), files),
+            vec!["README.md", "alias/file", "docs/index.md", "real/file"]
+        );
+    }
+
+    #[test]
+    fn replace_existing_file_with_symlink_and_directory() {
+        let output = tempfile::tempdir().unwrap();
+        write(output.path(), "README.md", "mine");
+        write(output.path(), "logs", "mine");
+
+        file_writer(
+            Path::new("README.md"),
+            &Content::Symlink(PathBuf::from("docs/index.md")),
+            0o644,
+            output.path(),
+        )
+        .unwrap();
+        file_writer(Path::new("logs"), &Content::Directory, 0, output.path())
+            .unwrap();
+
+        assert_eq!(
+            fs::read_link(output.path().join("README.md")).unwrap(),
+            PathBuf::from("docs/index.md")
+        );
+        assert!(output.path().join("logs").is_dir());
+    }
+
+    #[test]
+    fn report_status_of_each_file() {
+        let output = tempfile::tempdir().unwrap();
+        write(output.path(), "same.txt", "same");
+        write(output.path(), "changed.txt", "old");
+        let status = |path: &str, content: &str| {
+            file_status(
+                Path::new(path),
+                &Content::Text(content.to_owned()),
+                output.path(),
+            )
+        };
+
+        assert_eq!(status("new.txt", "new"), Outcome::Created);
+        assert_eq!(status("changed.txt", "new"), Outcome::Overwritten);
+        assert_eq!(status("same.txt", "same"), Outcome::Unchanged);
+    }
+
+    #[test]
+    fn report_creation_into_missing_output_directory() {
+        let output = tempfile::tempdir().unwrap();
+        let missing = output.path().join("project");
+
+        let result = file_status(
+            Path::new("src/main.rs"),
+            &Content::Text(String::new()),
+            &missing,
+        );
+
+        assert_eq!(result, Outcome::Created);
+        assert!(!missing.exists());
+    }
+
+    fn diff(path: &str, content: Content, output: &Path) -> (Outcome, String) {
+        let mut written = Vec::new();
+        let outcome =
+            write_diff(Path::new(path), &content, output, &mut written)
+                .unwrap();
+        (outcome, String::from_utf8(written).unwrap())
+    }
+
+    #[test]
+    fn diff_changed_text_file() {
+        let output = tempfile::tempdir().unwrap();
+        write(output.path(), "README.md", "old\n");
+
+        let result =
+            diff("README.md", Content::Text("new\n".to_owned()), output.path());
+
+        assert_eq!(
+            result,
+            (
+                Outcome::Overwritten,
+                "--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn diff_new_file_against_dev_null() {
+        let output = tempfile::tempdir().unwrap();
+
+        let result = diff(
+            "README.md",
+            Content::Text("new\n".to_owned()),
+            &output.path().join("missing"),
+        );
+
+        assert_eq!(
+            result,
+            (
+                Outcome::Created,
+                "--- /dev/null\n+++ b/README.md\n@@ -0,0 +1 @@\n+new\n"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn report_changed_binary_file() {
+        let output = tempfile::tempdir().unwrap();
+        fs::write(output.path().join("logo.png"), [0, 1, 2]).unwrap();
+
+        let result =
+            diff("logo.png", Content::Binary(vec![0, 1, 3]), output.path());
+
+        assert_eq!(
+            result,
+            (
+                Outcome::Overwritten,
+                "Binary files a/logo.png and b/logo.png differ\n".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn print_nothing_for_unchanged_file() {
+        let output = tempfile::tempdir().unwrap();
+        write(output.path(), "README.md", "same\n");
+
+        let result = diff(
+            "README.md",
+            Content::Text("same\n".to_owned()),
+            output.path(),
+        );
+
+        assert_eq!(result, (Outcome::Unchanged, String::new()));
+    }
+}
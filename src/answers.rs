@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub const ANSWERS_NAME: &str = ".skelly-answers.toml";
+
+const HEADER: &str =
+    "# Generated by skelly to regenerate or update this project.\n";
+
+#[derive(thiserror::Error, PartialEq, Debug)]
+pub enum Error {
+    #[error("Unable to read file")]
+    UnableToReadFile,
+    #[error("Unable to parse")]
+    UnableToParse,
+    #[error("Unable to write file")]
+    WriteFailed,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Answers {
+    pub skeleton: Skeleton,
+
+    #[serde(default)]
+    pub inputs: toml::Table,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Skeleton {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    pub path: PathBuf,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+}
+
+impl Answers {
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let content =
+            fs::read_to_string(path).or(Err(Error::UnableToReadFile))?;
+        toml::from_str(&content).or(Err(Error::UnableToParse))
+    }
+
+    pub fn to_file(&self, path: &Path) -> Result<(), Error> {
+        let content = toml::to_string(self).or(Err(Error::WriteFailed))?;
+        fs::write(path, format!("{HEADER}{content}"))
+            .or(Err(Error::WriteFailed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    impl FromStr for Answers {
+        type Err = Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            toml::from_str(s).or(Err(Error::UnableToParse))
+        }
+    }
+
+    #[test]
+    fn parse_from_string() {
+        let answers = Answers::from_str(
+            r#"
+            [skeleton]
+            name = "rust"
+            version = "1.2.0"
+            path = "/skeletons/rust"
+            revision = "abc123"
+
+            [inputs]
+            name = "example"
+            use_docker = true
+            "#,
+        )
+        .unwrap();
+
+        let mut inputs = toml::Table::new();
+        inputs.insert("name".to_owned(), "example".into());
+        inputs.insert("use_docker".to_owned(), true.into());
+        assert_eq!(
+            answers,
+            Answers {
+                skeleton: Skeleton {
+                    name: Some("rust".to_owned()),
+                    version: Some("1.2.0".to_owned()),
+                    path: PathBuf::from("/skeletons/rust"),
+                    revision: Some("abc123".to_owned()),
+                },
+                inputs,
+            },
+        );
+    }
+
+    #[test]
+    fn serialize_and_parse_back() {
+        let mut inputs = toml::Table::new();
+        inputs.insert(
+            "services".to_owned(),
+            toml::Value::Array(vec!["api".into(), "worker".into()]),
+        );
+        let answers = Answers {
+            skeleton: Skeleton {
+                path: PathBuf::from("/skeletons/rust"),
+                ..Default::default()
+            },
+            inputs,
+        };
+
+        let content = toml::to_string(&answers).unwrap();
+
+        assert_eq!(Answers::from_str(&content), Ok(answers));
+    }
+}
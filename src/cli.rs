@@ -1,14 +1,23 @@
-use crate::config::Config;
+use crate::answers::Answers;
+use crate::config::{self, CONFIG_NAME, Config};
+use crate::inputs;
 use clap::Parser;
 use std::error::Error;
 use std::path::Path;
-use std::{fs::create_dir_all, path::PathBuf};
-
-const CONFIG_NAME: &str = "skelly.toml";
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    args_conflicts_with_subcommands = true,
+)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Which skeleton to use
     #[arg(
         short('s'),
@@ -48,15 +57,130 @@ pub struct Args {
         long("library-dir"),
         value_name = "DIRECTORY",
         value_hint = clap::ValueHint::DirPath,
-        value_parser = parse_library_dir,
+        value_parser = parse_directory,
     )]
     pub library_dir: Option<PathBuf>,
 
+    /// Answers file with inputs from a previous run
+    #[arg(
+        long("answers-file"),
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = parse_answers_file,
+    )]
+    pub answers: Option<Answers>,
+
+    /// TOML, JSON or YAML file with inputs passed to the skeleton
+    #[arg(
+        long("inputs-file"),
+        value_name = "FILE",
+        value_hint = clap::ValueHint::FilePath,
+        value_parser = parse_inputs_file,
+    )]
+    pub inputs_file: Option<toml::Table>,
+
+    /// Fail on inputs that are not declared by the skeleton
+    #[arg(long, requires = "skeleton_config")]
+    pub strict_inputs: bool,
+
+    /// Report which files would be created or overwritten without writing them
+    #[arg(long, requires = "skeleton_config")]
+    pub dry_run: bool,
+
+    /// Show a diff between the generated files and the existing ones without
+    /// writing them
+    #[arg(long, requires = "skeleton_config", conflicts_with = "dry_run")]
+    pub diff: bool,
+
+    /// What to do when a generated file already exists
+    #[arg(
+        long,
+        value_name = "POLICY",
+        value_enum,
+        default_value_t = OnConflict::Error,
+    )]
+    pub on_conflict: OnConflict,
+
     /// Inputs passed to the skeleton
     #[arg(value_parser = parse_key_val::<String, String>)]
     pub inputs: Vec<(String, String)>,
 }
 
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Update a generated project to the current version of its skeleton
+    Update(UpdateArgs),
+    /// Show the inputs and files of a skeleton
+    Inspect(InspectArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct InspectArgs {
+    /// Which skeleton to inspect
+    #[arg(
+        short('s'),
+        long("skeleton-path"),
+        value_name = "DIRECTORY",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = parse_skeleton_config,
+    )]
+    pub skeleton_config: Config,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = InspectFormat::Table)]
+    pub format: InspectFormat,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum InspectFormat {
+    Table,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct UpdateArgs {
+    /// Which skeleton to update to [default: the one in the answers file]
+    #[arg(
+        short('s'),
+        long("skeleton-path"),
+        value_name = "DIRECTORY",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = parse_skeleton_config,
+    )]
+    pub skeleton_config: Option<Config>,
+
+    /// Generated project to update
+    #[arg(
+        short,
+        long,
+        value_name = "DIRECTORY",
+        default_value = ".",
+        value_hint = clap::ValueHint::DirPath,
+        value_parser = parse_directory,
+    )]
+    pub output_path: PathBuf,
+
+    /// Fail on inputs that are not declared by the skeleton
+    #[arg(long)]
+    pub strict_inputs: bool,
+
+    /// Inputs that override the ones in the answers file
+    #[arg(value_parser = parse_key_val::<String, String>)]
+    pub inputs: Vec<(String, String)>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum OnConflict {
+    /// Keep the existing file
+    Skip,
+    /// Replace the existing file
+    Overwrite,
+    /// Keep the existing file and fail before writing anything
+    Error,
+    /// Ask whether to replace the existing file
+    Prompt,
+}
+
 fn parse_skeleton_config(value: &str) -> Result<Config, String> {
     let path = Path::new(value);
     if !path.is_dir() {
@@ -75,8 +199,19 @@ fn parse_skeleton_config(value: &str) -> Result<Config, String> {
         ));
     }
 
-    Config::from_file(&config_path)
-        .or(Err("unable to parse config.".to_string()))
+    Config::from_file(&config_path).map_err(|e| match e {
+        config::Error::IncompatibleVersion(required, current) => format!(
+            "skeleton requires skelly {required} or newer, running {current}."
+        ),
+        config::Error::MissingTemplateDirectory(directory) => format!(
+            "template directory '{}' does not exist.",
+            directory.display()
+        ),
+        config::Error::ReservedInputName(name) => format!(
+            "input '{name}' is reserved for the items of repeated files."
+        ),
+        _ => "unable to parse config.".to_string(),
+    })
 }
 
 fn parse_file_path(value: &str) -> Result<PathBuf, String> {
@@ -99,19 +234,18 @@ fn parse_file_path(value: &str) -> Result<PathBuf, String> {
 fn parse_output_path(value: &str) -> Result<PathBuf, String> {
     let path = PathBuf::from(value);
 
-    if path.exists() {
-        if !path.is_dir() {
-            return Err(format!("'{value}' is not a directory."));
-        }
-    } else {
-        create_dir_all(&path)
-            .or(Err(format!("unable to create directory '{value}'.")))?;
+    if !path.exists() {
+        return std::path::absolute(&path)
+            .or(Err(format!("unable to resolve path '{value}'.")));
+    }
+    if !path.is_dir() {
+        return Err(format!("'{value}' is not a directory."));
     }
 
     path.canonicalize().or(Err(format!("unable to resolve path '{value}'.")))
 }
 
-fn parse_library_dir(value: &str) -> Result<PathBuf, String> {
+fn parse_directory(value: &str) -> Result<PathBuf, String> {
     let path = Path::new(value);
     if !path.is_dir() {
         return Err(format!("'{value}' is not a directory."));
@@ -120,6 +254,24 @@ fn parse_library_dir(value: &str) -> Result<PathBuf, String> {
     path.canonicalize().or(Err(format!("unable to resolve path '{value}'.")))
 }
 
+fn parse_answers_file(value: &str) -> Result<Answers, String> {
+    let path = parse_file_path(value)?;
+
+    Answers::from_file(&path)
+        .or(Err("unable to parse answers file.".to_string()))
+}
+
+fn parse_inputs_file(value: &str) -> Result<toml::Table, String> {
+    let path = parse_file_path(value)?;
+
+    inputs::from_file(&path).map_err(|e| match e {
+        inputs::Error::UnsupportedFormat => {
+            "unsupported format, use a .toml, .json or .yaml file.".to_string()
+        }
+        _ => "unable to parse inputs file.".to_string(),
+    })
+}
+
 fn parse_key_val<T, U>(
     s: &str,
 ) -> Result<(T, U), Box<dyn Error + Send + Sync + 'static>>
@@ -1,9 +1,14 @@
+use crate::usecases::render_skeleton::ITEM_NAME;
 use serde::{Deserialize, Deserializer, Serialize, de};
 use std::{
-    fs,
+    fmt, fs,
     path::{Path, PathBuf},
 };
 
+pub const CONFIG_NAME: &str = "skelly.toml";
+
+pub const IGNORE_NAME: &str = ".skellyignore";
+
 const DEFAULT_TEMPLATE_DIRECTORY: &str = "skeleton";
 
 #[derive(thiserror::Error, PartialEq, Debug)]
@@ -12,46 +17,285 @@ pub enum Error {
     UnableToReadFile,
     #[error("Unable to parse")]
     UnableToParse,
+    #[error("Skeleton requires skelly {0} or newer, running {1}")]
+    IncompatibleVersion(String, String),
+    #[error("Template directory '{}' does not exist", .0.display())]
+    MissingTemplateDirectory(PathBuf),
+    #[error("Input '{0}' is reserved for the items of repeated files")]
+    ReservedInputName(String),
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Config {
-    #[serde(skip, default = "default_template_directory")]
-    pub template_directory: PathBuf,
+    #[serde(skip)]
+    pub skeleton_directory: PathBuf,
+
+    #[serde(default)]
+    pub skeleton: Metadata,
+
+    #[serde(
+        default = "default_template_directories",
+        rename = "template_directory",
+        deserialize_with = "deserialize_template_directories"
+    )]
+    pub template_directories: Vec<PathBuf>,
+
+    #[serde(default)]
+    pub ignore: Vec<String>,
+
+    #[serde(default)]
+    pub copy: Vec<String>,
+
+    #[serde(default)]
+    pub binary: Vec<String>,
+
+    #[serde(default)]
+    pub files: Vec<FileRule>,
+
+    #[serde(default)]
+    pub dereference_symlinks: bool,
 
     pub inputs: Vec<Input>,
+
+    #[serde(default)]
+    pub computed: Vec<Computed>,
 }
 
 impl Config {
     pub fn from_file(path: &Path) -> Result<Self, Error> {
         let skeleton_directory =
             path.parent().unwrap_or(Path::new(".")).to_owned();
-        let template_directory =
-            skeleton_directory.join(default_template_directory()).to_owned();
         let content =
             fs::read_to_string(path).or(Err(Error::UnableToReadFile))?;
         let result: Self =
             toml::from_str(&content).or(Err(Error::UnableToParse))?;
-        Ok(Self { template_directory, ..result })
+        if let Some(min_version) = &result.skeleton.min_skelly_version {
+            check_version(min_version, env!("CARGO_PKG_VERSION"))?;
+        }
+        check_reserved_inputs(&result)?;
+        let template_directories = resolve_template_directories(
+            &skeleton_directory,
+            &result.template_directories,
+        )?;
+        Ok(Self { skeleton_directory, template_directories, ..result })
     }
 }
 
-fn default_template_directory() -> PathBuf {
-    Path::new(DEFAULT_TEMPLATE_DIRECTORY).to_owned()
+fn resolve_template_directories(
+    skeleton_directory: &Path,
+    template_directories: &[PathBuf],
+) -> Result<Vec<PathBuf>, Error> {
+    template_directories
+        .iter()
+        .map(|directory| {
+            let directory = skeleton_directory.join(directory);
+            match directory.is_dir() {
+                true => Ok(directory),
+                false => Err(Error::MissingTemplateDirectory(directory)),
+            }
+        })
+        .collect()
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+fn check_reserved_inputs(config: &Config) -> Result<(), Error> {
+    if !config.files.iter().any(|f| f.each.is_some()) {
+        return Ok(());
+    }
+
+    let names = config.inputs.iter().map(|i| &i.name);
+    let computed_names = config.computed.iter().map(|c| &c.name);
+    match names.chain(computed_names).find(|name| *name == ITEM_NAME) {
+        Some(name) => Err(Error::ReservedInputName(name.to_owned())),
+        None => Ok(()),
+    }
+}
+
+fn check_version(
+    min_version: &str,
+    current_version: &str,
+) -> Result<(), Error> {
+    let requirement = semver::VersionReq::parse(&format!(">={min_version}"))
+        .or(Err(Error::UnableToParse))?;
+    let current = semver::Version::parse(current_version)
+        .or(Err(Error::UnableToParse))?;
+    if requirement.matches(&current) {
+        Ok(())
+    } else {
+        Err(Error::IncompatibleVersion(
+            min_version.to_owned(),
+            current_version.to_owned(),
+        ))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Metadata {
+    pub name: Option<String>,
+
+    pub version: Option<String>,
+
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub authors: Vec<String>,
+
+    pub min_skelly_version: Option<String>,
+}
+
+fn default_template_directories() -> Vec<PathBuf> {
+    vec![Path::new(DEFAULT_TEMPLATE_DIRECTORY).to_owned()]
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Input {
     pub name: String,
 
+    #[serde(default, rename = "type")]
+    pub kind: InputType,
+
     #[serde(default, deserialize_with = "deserialize_default")]
     pub default: Option<String>,
 
     #[serde(default, deserialize_with = "deserialize_options")]
     pub options: Option<Vec<String>>,
+
+    #[serde(default, deserialize_with = "deserialize_pattern")]
+    pub pattern: Option<String>,
+
+    pub min_length: Option<usize>,
+
+    pub max_length: Option<usize>,
+
+    pub min: Option<f64>,
+
+    pub max: Option<f64>,
+
+    pub when: Option<String>,
+
+    pub env: Option<String>,
+
+    pub description: Option<String>,
+
+    pub help: Option<String>,
+
+    pub example: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct FileRule {
+    pub glob: String,
+
+    pub when: Option<String>,
+
+    pub each: Option<String>,
 }
 
-fn value_to_string(value: &toml::Value) -> String {
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct Computed {
+    pub name: String,
+
+    #[serde(default, rename = "type")]
+    pub kind: InputType,
+
+    pub value: String,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputType {
+    #[default]
+    String,
+    Bool,
+    Integer,
+    Float,
+    List,
+    Map,
+}
+
+impl InputType {
+    pub fn parse(&self, value: &str) -> Option<toml::Value> {
+        match self {
+            Self::String => Some(toml::Value::String(value.to_owned())),
+            Self::Bool => parse_bool(value).map(toml::Value::Boolean),
+            Self::Integer => {
+                value.trim().parse().ok().map(toml::Value::Integer)
+            }
+            Self::Float => value.trim().parse().ok().map(toml::Value::Float),
+            Self::List => parse_list(value),
+            Self::Map => parse_map(value),
+        }
+    }
+
+    pub fn convert(&self, value: &toml::Value) -> Option<toml::Value> {
+        match (self, value) {
+            (_, toml::Value::String(v)) => self.parse(v),
+            (Self::String, v) => Some(toml::Value::String(value_to_string(v))),
+            (Self::Float, toml::Value::Integer(v)) => {
+                Some(toml::Value::Float(*v as f64))
+            }
+            (Self::Bool, toml::Value::Boolean(_))
+            | (Self::Integer, toml::Value::Integer(_))
+            | (Self::Float, toml::Value::Float(_))
+            | (Self::List, toml::Value::Array(_))
+            | (Self::Map, toml::Value::Table(_)) => Some(value.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::String => "string",
+            Self::Bool => "bool",
+            Self::Integer => "integer",
+            Self::Float => "float",
+            Self::List => "list",
+            Self::Map => "map",
+        };
+        write!(f, "{name}")
+    }
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_list(value: &str) -> Option<toml::Value> {
+    let value = value.trim();
+    if value.starts_with('[') {
+        return parse_inline_value(value).filter(|v| v.is_array());
+    }
+
+    let items = value
+        .split(',')
+        .map(str::trim)
+        .filter(|i| !i.is_empty())
+        .map(|i| toml::Value::String(i.to_owned()))
+        .collect();
+    Some(toml::Value::Array(items))
+}
+
+fn parse_map(value: &str) -> Option<toml::Value> {
+    let value = value.trim();
+    if value.starts_with('{') {
+        return parse_inline_value(value).filter(|v| v.is_table());
+    }
+
+    toml::from_str(value).ok().map(toml::Value::Table)
+}
+
+fn parse_inline_value(value: &str) -> Option<toml::Value> {
+    let mut table: toml::Table =
+        toml::from_str(&format!("value = {value}")).ok()?;
+    table.remove("value")
+}
+
+pub fn value_to_string(value: &toml::Value) -> String {
     match value {
         toml::Value::String(v) => v.to_owned(),
         v => v.to_string(),
@@ -85,6 +329,46 @@ where
     Ok(Some(values))
 }
 
+fn deserialize_template_directories<'de, D>(
+    deserializer: D,
+) -> Result<Vec<PathBuf>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: toml::Value = Deserialize::deserialize(deserializer)?;
+    let directories = match value {
+        toml::Value::String(directory) => vec![directory],
+        toml::Value::Array(directories) => directories
+            .into_iter()
+            .map(|d| d.as_str().map(str::to_owned))
+            .collect::<Option<_>>()
+            .ok_or(de::Error::custom(
+                "unable to deserialize template_directory",
+            ))?,
+        _ => {
+            return Err(de::Error::custom(
+                "unable to deserialize template_directory",
+            ));
+        }
+    };
+    if directories.is_empty() {
+        return Err(de::Error::custom("template_directory is empty"));
+    }
+
+    Ok(directories.into_iter().map(PathBuf::from).collect())
+}
+
+fn deserialize_pattern<'de, D>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let pattern: String = Deserialize::deserialize(deserializer)?;
+    regex::Regex::new(&pattern).map_err(de::Error::custom)?;
+    Ok(Some(pattern))
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
@@ -101,8 +385,16 @@ mod tests {
 
     fn default_config() -> Config {
         Config {
-            template_directory: default_template_directory(),
+            skeleton_directory: PathBuf::new(),
+            skeleton: Metadata::default(),
+            template_directories: default_template_directories(),
+            ignore: Vec::new(),
+            copy: Vec::new(),
+            binary: Vec::new(),
+            files: Vec::new(),
+            dereference_symlinks: false,
             inputs: Vec::new(),
+            computed: Vec::new(),
         }
     }
 
@@ -123,6 +415,7 @@ mod tests {
                     name: "example".to_owned(),
                     default: None,
                     options: None,
+                    ..Default::default()
                 }],
                 ..default_config()
             },
@@ -147,6 +440,7 @@ mod tests {
                     name: "example".to_owned(),
                     default: Some("42".to_owned()),
                     options: None,
+                    ..Default::default()
                 }],
                 ..default_config()
             },
@@ -175,9 +469,434 @@ mod tests {
                         "2".to_owned(),
                         "3".to_owned()
                     ]),
+                    ..Default::default()
                 }],
                 ..default_config()
             },
         );
     }
+
+    #[test]
+    fn parse_input_type() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "example"
+            type = "bool"
+            default = false
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.inputs[0].kind, InputType::Bool);
+        assert_eq!(config.inputs[0].default, Some("false".to_owned()));
+    }
+
+    #[test]
+    fn parse_scalar_values_by_type() {
+        assert_eq!(
+            InputType::Bool.parse("false"),
+            Some(toml::Value::Boolean(false))
+        );
+        assert_eq!(InputType::Bool.parse("maybe"), None);
+        assert_eq!(
+            InputType::Integer.parse("42"),
+            Some(toml::Value::Integer(42))
+        );
+        assert_eq!(InputType::Integer.parse("4.2"), None);
+        assert_eq!(
+            InputType::Float.parse("4.2"),
+            Some(toml::Value::Float(4.2))
+        );
+    }
+
+    #[test]
+    fn parse_list_values() {
+        let want = Some(toml::Value::Array(vec![
+            toml::Value::String("a".to_owned()),
+            toml::Value::String("b".to_owned()),
+        ]));
+
+        assert_eq!(InputType::List.parse("a, b"), want);
+        assert_eq!(InputType::List.parse(r#"["a", "b"]"#), want);
+        assert_eq!(InputType::List.parse(""), Some(toml::Value::Array(vec![])));
+    }
+
+    #[test]
+    fn parse_map_values() {
+        let mut table = toml::Table::new();
+        table.insert("port".to_owned(), toml::Value::Integer(80));
+        let want = Some(toml::Value::Table(table));
+
+        assert_eq!(InputType::Map.parse("{ port = 80 }"), want);
+        assert_eq!(InputType::Map.parse("port = 80"), want);
+        assert_eq!(InputType::Map.parse("[1, 2]"), None);
+    }
+
+    #[test]
+    fn parse_constraints() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "example"
+            pattern = "^[a-z]+$"
+            min_length = 2
+            max_length = 8
+            min = 1
+            max = 9.5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.inputs[0],
+            Input {
+                name: "example".to_owned(),
+                pattern: Some("^[a-z]+$".to_owned()),
+                min_length: Some(2),
+                max_length: Some(8),
+                min: Some(1.0),
+                max: Some(9.5),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn fail_to_parse_invalid_pattern() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "example"
+            pattern = "[a-z"
+            "#,
+        );
+
+        assert_eq!(config, Err(Error::UnableToParse));
+    }
+
+    #[test]
+    fn parse_when_condition() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "docker_registry"
+            when = "use_docker"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.inputs[0].when, Some("use_docker".to_owned()));
+    }
+
+    #[test]
+    fn parse_computed_values() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "name"
+
+            [[computed]]
+            name = "crate_name"
+            value = "{{ name | lower }}"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.computed,
+            vec![Computed {
+                name: "crate_name".to_owned(),
+                value: "{{ name | lower }}".to_owned(),
+                ..Default::default()
+            }],
+        );
+    }
+
+    #[test]
+    fn convert_structured_values_by_type() {
+        let list = toml::Value::Array(vec!["a".into()]);
+
+        assert_eq!(InputType::List.convert(&list), Some(list.clone()));
+        assert_eq!(InputType::Map.convert(&list), None);
+        assert_eq!(
+            InputType::Float.convert(&toml::Value::Integer(1)),
+            Some(toml::Value::Float(1.0))
+        );
+        assert_eq!(
+            InputType::String.convert(&toml::Value::Integer(1)),
+            Some(toml::Value::String("1".to_owned()))
+        );
+        assert_eq!(
+            InputType::Bool.convert(&toml::Value::String("yes".to_owned())),
+            Some(toml::Value::Boolean(true))
+        );
+    }
+
+    #[test]
+    fn parse_env_variable_name() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "project_name"
+            env = "CI_PROJECT_NAME"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.inputs[0].env, Some("CI_PROJECT_NAME".to_owned()));
+    }
+
+    #[test]
+    fn parse_input_documentation() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "crate_name"
+            description = "Name of the crate"
+            help = "Lower case letters, digits and underscores."
+            example = "my_crate"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.inputs[0],
+            Input {
+                name: "crate_name".to_owned(),
+                description: Some("Name of the crate".to_owned()),
+                help: Some(
+                    "Lower case letters, digits and underscores.".to_owned()
+                ),
+                example: Some("my_crate".to_owned()),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn parse_skeleton_metadata() {
+        let config = Config::from_str(
+            r#"
+            inputs = []
+
+            [skeleton]
+            name = "rust"
+            version = "1.2.0"
+            description = "A Rust project"
+            authors = ["Jane Doe"]
+            min_skelly_version = "1.3"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.skeleton,
+            Metadata {
+                name: Some("rust".to_owned()),
+                version: Some("1.2.0".to_owned()),
+                description: Some("A Rust project".to_owned()),
+                authors: vec!["Jane Doe".to_owned()],
+                min_skelly_version: Some("1.3".to_owned()),
+            },
+        );
+    }
+
+    #[test]
+    fn accept_skelly_version_at_or_above_minimum() {
+        assert_eq!(check_version("1.3", "1.3.0"), Ok(()));
+        assert_eq!(check_version("1.2.5", "1.3.0"), Ok(()));
+    }
+
+    #[test]
+    fn reject_skelly_version_below_minimum() {
+        assert_eq!(
+            check_version("2.0", "1.3.0"),
+            Err(Error::IncompatibleVersion(
+                "2.0".to_owned(),
+                "1.3.0".to_owned()
+            )),
+        );
+        assert_eq!(check_version("two", "1.3.0"), Err(Error::UnableToParse));
+    }
+
+    #[test]
+    fn parse_single_template_directory() {
+        let config = Config::from_str(
+            r#"
+            template_directory = "template"
+            inputs = []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.template_directories,
+            vec![PathBuf::from("template")]
+        );
+    }
+
+    #[test]
+    fn parse_several_template_directories() {
+        let config = Config::from_str(
+            r#"
+            template_directory = ["base", "extra"]
+            inputs = []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.template_directories,
+            vec![PathBuf::from("base"), PathBuf::from("extra")]
+        );
+    }
+
+    #[test]
+    fn fail_to_parse_empty_template_directories() {
+        let result = Config::from_str(
+            r#"
+            template_directory = []
+            inputs = []
+            "#,
+        );
+
+        assert_eq!(result, Err(Error::UnableToParse));
+    }
+
+    #[test]
+    fn resolve_template_directories_relative_to_config() {
+        let skeleton = tempfile::tempdir().unwrap();
+        fs::create_dir(skeleton.path().join("template")).unwrap();
+
+        let result = resolve_template_directories(
+            skeleton.path(),
+            &[PathBuf::from("template")],
+        );
+
+        assert_eq!(result, Ok(vec![skeleton.path().join("template")]));
+    }
+
+    #[test]
+    fn fail_on_missing_template_directory() {
+        let skeleton = tempfile::tempdir().unwrap();
+
+        let result = resolve_template_directories(
+            skeleton.path(),
+            &[PathBuf::from("missing")],
+        );
+
+        assert_eq!(
+            result,
+            Err(Error::MissingTemplateDirectory(
+                skeleton.path().join("missing")
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_ignore_patterns() {
+        let config = Config::from_str(
+            r#"
+            ignore = ["**/*.swp", ".git/"]
+            inputs = []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.ignore,
+            vec!["**/*.swp".to_owned(), ".git/".to_owned()]
+        );
+    }
+
+    #[test]
+    fn parse_copy_globs() {
+        let config = Config::from_str(
+            r#"
+            copy = ["**/*.j2", ".github/**"]
+            inputs = []
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.copy,
+            vec!["**/*.j2".to_owned(), ".github/**".to_owned()]
+        );
+    }
+
+    #[test]
+    fn parse_file_rules() {
+        let config = Config::from_str(
+            r#"
+            inputs = []
+
+            [[files]]
+            glob = "docker/**"
+            when = "{{ use_docker }}"
+
+            [[files]]
+            glob = "services/**"
+            each = "services"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.files,
+            vec![
+                FileRule {
+                    glob: "docker/**".to_owned(),
+                    when: Some("{{ use_docker }}".to_owned()),
+                    each: None,
+                },
+                FileRule {
+                    glob: "services/**".to_owned(),
+                    when: None,
+                    each: Some("services".to_owned()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_symlinks_by_default() {
+        let config = Config::from_str("inputs = []").unwrap();
+
+        assert!(!config.dereference_symlinks);
+    }
+
+    #[test]
+    fn reject_item_input_when_files_repeat() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "item"
+
+            [[files]]
+            glob = "services/**"
+            each = "services"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_reserved_inputs(&config),
+            Err(Error::ReservedInputName("item".to_owned()))
+        );
+    }
+
+    #[test]
+    fn accept_item_input_when_no_files_repeat() {
+        let config = Config::from_str(
+            r#"
+            [[inputs]]
+            name = "item"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(check_reserved_inputs(&config), Ok(()));
+    }
 }
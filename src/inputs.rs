@@ -0,0 +1,130 @@
+use std::{fs, path::Path};
+
+#[derive(thiserror::Error, PartialEq, Debug)]
+pub enum Error {
+    #[error("Unable to read file")]
+    UnableToReadFile,
+    #[error("Unable to parse")]
+    UnableToParse,
+    #[error("Unsupported format")]
+    UnsupportedFormat,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Result<Self, Error> {
+        let extension =
+            path.extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase());
+        match extension.as_deref() {
+            Some("toml") => Ok(Self::Toml),
+            Some("json") => Ok(Self::Json),
+            Some("yaml" | "yml") => Ok(Self::Yaml),
+            _ => Err(Error::UnsupportedFormat),
+        }
+    }
+}
+
+pub fn from_file(path: &Path) -> Result<toml::Table, Error> {
+    let format = Format::from_path(path)?;
+    let content = fs::read_to_string(path).or(Err(Error::UnableToReadFile))?;
+    from_str(&content, format)
+}
+
+pub fn from_str(content: &str, format: Format) -> Result<toml::Table, Error> {
+    match format {
+        Format::Toml => toml::from_str(content).or(Err(Error::UnableToParse)),
+        Format::Json => {
+            serde_json::from_str(content).or(Err(Error::UnableToParse))
+        }
+        Format::Yaml => {
+            serde_yaml::from_str(content).or(Err(Error::UnableToParse))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_inputs() -> toml::Table {
+        let mut ports = toml::Table::new();
+        ports.insert("http".to_owned(), toml::Value::Integer(80));
+
+        let mut inputs = toml::Table::new();
+        inputs.insert("name".to_owned(), "example".into());
+        inputs.insert("use_docker".to_owned(), true.into());
+        inputs.insert(
+            "services".to_owned(),
+            toml::Value::Array(vec!["api".into(), "worker".into()]),
+        );
+        inputs.insert("ports".to_owned(), toml::Value::Table(ports));
+        inputs
+    }
+
+    #[test]
+    fn detect_format_from_extension() {
+        assert_eq!(Format::from_path(Path::new("a.toml")), Ok(Format::Toml));
+        assert_eq!(Format::from_path(Path::new("a.JSON")), Ok(Format::Json));
+        assert_eq!(Format::from_path(Path::new("a.yml")), Ok(Format::Yaml));
+        assert_eq!(
+            Format::from_path(Path::new("a.txt")),
+            Err(Error::UnsupportedFormat)
+        );
+    }
+
+    #[test]
+    fn parse_toml() {
+        let result = from_str(
+            r#"
+            name = "example"
+            use_docker = true
+            services = ["api", "worker"]
+            ports = { http = 80 }
+            "#,
+            Format::Toml,
+        );
+
+        assert_eq!(result, Ok(make_inputs()));
+    }
+
+    #[test]
+    fn parse_json() {
+        let result = from_str(
+            r#"{
+                "name": "example",
+                "use_docker": true,
+                "services": ["api", "worker"],
+                "ports": { "http": 80 }
+            }"#,
+            Format::Json,
+        );
+
+        assert_eq!(result, Ok(make_inputs()));
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let result = from_str(
+            "name: example\n\
+             use_docker: true\n\
+             services:\n  - api\n  - worker\n\
+             ports:\n  http: 80\n",
+            Format::Yaml,
+        );
+
+        assert_eq!(result, Ok(make_inputs()));
+    }
+
+    #[test]
+    fn fail_to_parse_null_values() {
+        let result = from_str(r#"{ "name": null }"#, Format::Json);
+
+        assert_eq!(result, Err(Error::UnableToParse));
+    }
+}
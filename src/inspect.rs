@@ -0,0 +1,149 @@
+use crate::config::{Config, Input, Metadata};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Inspection<'a> {
+    skeleton: &'a Metadata,
+    inputs: &'a [Input],
+    files: &'a [String],
+}
+
+pub fn to_table(config: &Config, files: &[String]) -> String {
+    let mut result = metadata_lines(&config.skeleton);
+
+    let header = ["NAME", "TYPE", "DEFAULT", "OPTIONS", "DESCRIPTION"]
+        .map(str::to_owned)
+        .to_vec();
+    let rows: Vec<Vec<String>> = std::iter::once(header)
+        .chain(config.inputs.iter().map(|input| {
+            vec![
+                input.name.to_owned(),
+                input.kind.to_string(),
+                input.default.to_owned().unwrap_or_default(),
+                input.options.to_owned().unwrap_or_default().join(", "),
+                input.description.to_owned().unwrap_or_default(),
+            ]
+        }))
+        .collect();
+
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result.push_str("\nFILES\n");
+    for file in files {
+        result.push_str(&format!("{file}\n"));
+    }
+
+    result
+}
+
+fn metadata_lines(metadata: &Metadata) -> String {
+    let title = [&metadata.name, &metadata.version]
+        .into_iter()
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut lines = Vec::new();
+    if !title.is_empty() {
+        lines.push(title);
+    }
+    if let Some(description) = &metadata.description {
+        lines.push(description.to_owned());
+    }
+    if !metadata.authors.is_empty() {
+        lines.push(format!("Authors: {}", metadata.authors.join(", ")));
+    }
+    if let Some(min_version) = &metadata.min_skelly_version {
+        lines.push(format!("Requires skelly {min_version} or newer"));
+    }
+
+    match lines.is_empty() {
+        true => String::new(),
+        false => format!("{}\n\n", lines.join("\n")),
+    }
+}
+
+pub fn to_json(config: &Config, files: &[String]) -> Result<String, String> {
+    let inspection = Inspection {
+        skeleton: &config.skeleton,
+        inputs: &config.inputs,
+        files,
+    };
+    serde_json::to_string_pretty(&inspection).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::InputType;
+    use std::str::FromStr;
+
+    fn make_config() -> Config {
+        Config::from_str(
+            r#"
+            [skeleton]
+            name = "rust"
+            version = "1.0.0"
+            description = "A Rust project"
+
+            [[inputs]]
+            name = "build_tool"
+            options = ["rustc", "cargo"]
+            default = "cargo"
+            description = "Tool used to build the project"
+
+            [[inputs]]
+            name = "port"
+            type = "integer"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn format_inputs_and_files_as_table() {
+        let result = to_table(&make_config(), &["Cargo.toml".to_owned()]);
+
+        assert_eq!(
+            result,
+            "rust 1.0.0\n\
+             A Rust project\n\
+             \n\
+             NAME        TYPE     DEFAULT  OPTIONS       DESCRIPTION\n\
+             build_tool  string   cargo    rustc, cargo  Tool used to build the project\n\
+             port        integer\n\
+             \n\
+             FILES\n\
+             Cargo.toml\n"
+        );
+    }
+
+    #[test]
+    fn format_inputs_and_files_as_json() {
+        let result = to_json(&make_config(), &["Cargo.toml".to_owned()]);
+
+        let value: serde_json::Value =
+            serde_json::from_str(&result.unwrap()).unwrap();
+        assert_eq!(value["skeleton"]["name"], "rust");
+        assert_eq!(value["inputs"][0]["name"], "build_tool");
+        assert_eq!(value["inputs"][1]["type"], InputType::Integer.to_string());
+        assert_eq!(value["files"][0], "Cargo.toml");
+    }
+}
@@ -3,8 +3,12 @@ use std::io::IsTerminal;
 
 mod actions;
 mod adapters;
+mod answers;
 mod cli;
 mod config;
+mod inputs;
+mod inspect;
+mod prompt;
 mod renderer;
 mod usecases;
 mod validation;
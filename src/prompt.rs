@@ -0,0 +1,256 @@
+use crate::config::Input;
+use std::io::{BufRead, Write};
+
+#[derive(thiserror::Error, PartialEq, Debug)]
+#[error("Failed to prompt")]
+pub struct Error(pub String);
+
+pub fn ask<R, W>(
+    input: &Input,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<String, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    if let Some(help) = &input.help {
+        write_line(writer, help)?;
+    }
+
+    match &input.options {
+        Some(options) if !options.is_empty() => {
+            pick_option(input, options, reader, writer)
+        }
+        _ => ask_value(input, reader, writer),
+    }
+}
+
+pub fn confirm<R, W>(
+    question: &str,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<bool, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    let question = format!("{question} [y/N]: ");
+    loop {
+        let answer = read_answer(&question, reader, writer)?;
+        match answer.to_lowercase().as_str() {
+            "y" | "yes" => return Ok(true),
+            "" | "n" | "no" => return Ok(false),
+            _ => write_line(writer, "Please answer 'y' or 'n'.")?,
+        }
+    }
+}
+
+fn ask_value<R, W>(
+    input: &Input,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<String, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    let question = match (&input.default, &input.example) {
+        (Some(default), _) => format!("{} [{}]: ", input.name, default),
+        (None, Some(example)) => format!("{} (e.g. {}): ", input.name, example),
+        (None, None) => format!("{}: ", input.name),
+    };
+
+    loop {
+        let answer = read_answer(&question, reader, writer)?;
+        match (answer.is_empty(), &input.default) {
+            (false, _) => return Ok(answer),
+            (true, Some(default)) => return Ok(default.to_owned()),
+            (true, None) => write_line(writer, "A value is required.")?,
+        }
+    }
+}
+
+fn pick_option<R, W>(
+    input: &Input,
+    options: &[String],
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<String, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    write_line(writer, &format!("{}:", input.name))?;
+    for (index, option) in options.iter().enumerate() {
+        write_line(writer, &format!("  {}) {}", index + 1, option))?;
+    }
+
+    let default_index = input
+        .default
+        .as_ref()
+        .and_then(|d| options.iter().position(|o| o == d));
+    let question = match default_index {
+        Some(index) => format!("Choose an option [{}]: ", index + 1),
+        None => "Choose an option: ".to_owned(),
+    };
+
+    loop {
+        let answer = read_answer(&question, reader, writer)?;
+        if answer.is_empty() {
+            if let Some(index) = default_index {
+                return Ok(options[index].to_owned());
+            }
+        } else if let Some(option) = find_option(&answer, options) {
+            return Ok(option.to_owned());
+        }
+        write_line(writer, &format!("Invalid choice '{answer}'."))?;
+    }
+}
+
+fn find_option<'a>(answer: &str, options: &'a [String]) -> Option<&'a String> {
+    match answer.parse::<usize>() {
+        Ok(number) if (1..=options.len()).contains(&number) => {
+            options.get(number - 1)
+        }
+        _ => options.iter().find(|o| *o == answer),
+    }
+}
+
+fn read_answer<R, W>(
+    question: &str,
+    reader: &mut R,
+    writer: &mut W,
+) -> Result<String, Error>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(writer, "{question}").map_err(|e| Error(e.to_string()))?;
+    writer.flush().map_err(|e| Error(e.to_string()))?;
+
+    let mut answer = String::new();
+    let read =
+        reader.read_line(&mut answer).map_err(|e| Error(e.to_string()))?;
+    if read == 0 {
+        return Err(Error("Unexpected end of input.".to_owned()));
+    }
+
+    Ok(answer.trim().to_owned())
+}
+
+fn write_line<W: Write>(writer: &mut W, line: &str) -> Result<(), Error> {
+    writeln!(writer, "{line}").map_err(|e| Error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn make_input(default: Option<&str>, options: Option<Vec<&str>>) -> Input {
+        Input {
+            name: "test".to_owned(),
+            default: default.map(|d| d.to_owned()),
+            options: options
+                .map(|o| o.into_iter().map(|v| v.to_owned()).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn run(input: &Input, answers: &str) -> Result<String, Error> {
+        let mut reader = Cursor::new(answers.as_bytes().to_vec());
+        let mut writer = Vec::new();
+        ask(input, &mut reader, &mut writer)
+    }
+
+    #[test]
+    fn return_typed_value() {
+        let input = make_input(None, None);
+
+        let result = run(&input, "hello\n");
+
+        assert_eq!(result, Ok("hello".to_owned()));
+    }
+
+    #[test]
+    fn return_default_when_answer_is_empty() {
+        let input = make_input(Some("ok"), None);
+
+        let result = run(&input, "\n");
+
+        assert_eq!(result, Ok("ok".to_owned()));
+    }
+
+    #[test]
+    fn ask_again_when_required_answer_is_empty() {
+        let input = make_input(None, None);
+
+        let result = run(&input, "\nvalue\n");
+
+        assert_eq!(result, Ok("value".to_owned()));
+    }
+
+    #[test]
+    fn pick_option_by_number() {
+        let input = make_input(None, Some(vec!["rustc", "cargo"]));
+
+        let result = run(&input, "2\n");
+
+        assert_eq!(result, Ok("cargo".to_owned()));
+    }
+
+    #[test]
+    fn pick_option_by_value() {
+        let input = make_input(None, Some(vec!["rustc", "cargo"]));
+
+        let result = run(&input, "rustc\n");
+
+        assert_eq!(result, Ok("rustc".to_owned()));
+    }
+
+    #[test]
+    fn pick_default_option_when_answer_is_empty() {
+        let input = make_input(Some("cargo"), Some(vec!["rustc", "cargo"]));
+
+        let result = run(&input, "\n");
+
+        assert_eq!(result, Ok("cargo".to_owned()));
+    }
+
+    #[test]
+    fn ask_again_when_choice_is_invalid() {
+        let input = make_input(None, Some(vec!["rustc", "cargo"]));
+
+        let result = run(&input, "3\nmake\n1\n");
+
+        assert_eq!(result, Ok("rustc".to_owned()));
+    }
+
+    #[test]
+    fn return_error_on_end_of_input() {
+        let input = make_input(None, None);
+
+        let result = run(&input, "");
+
+        assert_eq!(result, Err(Error("Unexpected end of input.".to_owned())));
+    }
+
+    fn run_confirm(answers: &str) -> Result<bool, Error> {
+        let mut reader = Cursor::new(answers.as_bytes().to_vec());
+        let mut writer = Vec::new();
+        confirm("Overwrite?", &mut reader, &mut writer)
+    }
+
+    #[test]
+    fn confirm_with_yes() {
+        assert_eq!(run_confirm("y\n"), Ok(true));
+        assert_eq!(run_confirm("YES\n"), Ok(true));
+    }
+
+    #[test]
+    fn refuse_by_default() {
+        assert_eq!(run_confirm("\n"), Ok(false));
+        assert_eq!(run_confirm("maybe\nn\n"), Ok(false));
+    }
+}
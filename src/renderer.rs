@@ -1,3 +1,4 @@
+use crate::config::{Computed, FileRule};
 use std::collections::HashMap;
 use std::error::Error as StdError;
 use tera::{Context, Tera};
@@ -9,7 +10,7 @@ pub struct Error(pub String);
 pub fn render(
     library_dir: Option<&str>,
     template: &str,
-    inputs: &[(String, String)],
+    inputs: &[(String, toml::Value)],
 ) -> Result<String, Error> {
     let mut tera = match library_dir {
         Some(dir) => Tera::new(&format!("{dir}/**/*")).map_err(|e| {
@@ -21,7 +22,7 @@ pub fn render(
     };
     tera.autoescape_on(vec![]);
 
-    let data: HashMap<String, String> =
+    let data: HashMap<String, toml::Value> =
         inputs.iter().map(|i| (i.0.to_owned(), i.1.to_owned())).collect();
 
     let mut context = Context::new();
@@ -52,9 +53,63 @@ pub fn render(
     }
 }
 
+pub fn evaluate(
+    condition: &str,
+    inputs: &[(String, toml::Value)],
+) -> Result<bool, Error> {
+    let template = if condition.contains("{{") || condition.contains("{%") {
+        condition.to_owned()
+    } else {
+        format!("{{% if {condition} %}}true{{% else %}}false{{% endif %}}")
+    };
+    let result = render(None, &template, inputs)?;
+    Ok(!matches!(result.trim(), "" | "false" | "0"))
+}
+
+pub fn compute(
+    inputs: &[(String, toml::Value)],
+    computed: &[Computed],
+) -> Result<Vec<(String, toml::Value)>, Error> {
+    let mut result = inputs.to_vec();
+    for c in computed {
+        let rendered = render(None, &c.value, &result).map_err(|e| {
+            Error(format!("Unable to compute '{}'.\n    {}", c.name, e.0))
+        })?;
+        let value = c.kind.parse(&rendered).ok_or(Error(format!(
+            "Unable to compute '{}'.\n    Value '{}' is not a {}.",
+            c.name, rendered, c.kind
+        )))?;
+        result.push((c.name.to_owned(), value));
+    }
+    Ok(result)
+}
+
+pub fn excluded_files(
+    inputs: &[(String, toml::Value)],
+    files: &[FileRule],
+) -> Result<Vec<String>, Error> {
+    let mut result = Vec::new();
+    for f in files {
+        let Some(condition) = &f.when else {
+            continue;
+        };
+        let included = evaluate(condition, inputs).map_err(|e| {
+            Error(format!(
+                "Unable to evaluate condition of files '{}'.\n    {}",
+                f.glob, e.0
+            ))
+        })?;
+        if !included {
+            result.push(f.glob.to_owned());
+        }
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::InputType;
 
     #[test]
     fn return_same() {
@@ -68,12 +123,33 @@ mod tests {
         let result = render(
             None,
             "Hello {{ name }}",
-            &[("name".to_owned(), "John".to_owned())],
+            &[("name".to_owned(), toml::Value::String("John".to_owned()))],
         );
 
         assert_eq!("Hello John", result.unwrap());
     }
 
+    #[test]
+    fn render_with_typed_inputs() {
+        let result = render(
+            None,
+            "{% if enabled %}on{% else %}off{% endif %}\
+             {% for i in items %} {{ i }}{% endfor %}",
+            &[
+                ("enabled".to_owned(), toml::Value::Boolean(false)),
+                (
+                    "items".to_owned(),
+                    toml::Value::Array(vec![
+                        toml::Value::Integer(1),
+                        toml::Value::Integer(2),
+                    ]),
+                ),
+            ],
+        );
+
+        assert_eq!("off 1 2", result.unwrap());
+    }
+
     #[test]
     fn error_when_missing_input() {
         let expected = "Variable `name` not found".to_string();
@@ -81,4 +157,78 @@ mod tests {
 
         assert_eq!(result, Err(Error(expected)));
     }
+
+    #[test]
+    fn evaluate_expression() {
+        let inputs = [
+            ("use_docker".to_owned(), toml::Value::Boolean(true)),
+            ("build_tool".to_owned(), toml::Value::String("rustc".to_owned())),
+        ];
+
+        assert_eq!(evaluate("use_docker", &inputs), Ok(true));
+        assert_eq!(evaluate("build_tool == 'cargo'", &inputs), Ok(false));
+    }
+
+    #[test]
+    fn evaluate_template() {
+        let inputs = [("use_docker".to_owned(), toml::Value::Boolean(false))];
+
+        assert_eq!(evaluate("{{ use_docker }}", &inputs), Ok(false));
+        assert_eq!(
+            evaluate("{% if not use_docker %}yes{% endif %}", &inputs),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn compute_values_from_inputs() {
+        let inputs =
+            [("name".to_owned(), toml::Value::String("My App".to_owned()))];
+        let computed = [
+            Computed {
+                name: "slug".to_owned(),
+                value: "{{ name | lower | replace(from=' ', to='-') }}"
+                    .to_owned(),
+                ..Default::default()
+            },
+            Computed {
+                name: "is_app".to_owned(),
+                kind: InputType::Bool,
+                value: "{{ slug is ending_with('app') }}".to_owned(),
+            },
+        ];
+
+        let result = compute(&inputs, &computed);
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                ("name".to_owned(), toml::Value::String("My App".to_owned())),
+                ("slug".to_owned(), toml::Value::String("my-app".to_owned())),
+                ("is_app".to_owned(), toml::Value::Boolean(true)),
+            ])
+        );
+    }
+
+    #[test]
+    fn exclude_files_whose_condition_is_false() {
+        let inputs = [("use_docker".to_owned(), toml::Value::Boolean(false))];
+        let files = [
+            FileRule {
+                glob: "docker/**".to_owned(),
+                when: Some("use_docker".to_owned()),
+                ..Default::default()
+            },
+            FileRule {
+                glob: "ci/**".to_owned(),
+                when: Some("not use_docker".to_owned()),
+                ..Default::default()
+            },
+            FileRule { glob: "src/**".to_owned(), ..Default::default() },
+        ];
+
+        let result = excluded_files(&inputs, &files);
+
+        assert_eq!(result, Ok(vec!["docker/**".to_owned()]));
+    }
 }
@@ -1,2 +1,3 @@
 pub mod render_skeleton;
 pub mod render_text;
+pub mod update_skeleton;
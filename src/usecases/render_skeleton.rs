@@ -1,6 +1,6 @@
 use std::{
-    fs,
-    os::unix::fs::{MetadataExt, PermissionsExt},
+    collections::{HashMap, HashSet},
+    os::unix::fs::MetadataExt,
     path::{Path, PathBuf},
 };
 
@@ -8,27 +8,617 @@ use std::{
 #[error("Failed to render skeleton")]
 pub struct Error(pub String);
 
-pub fn execute<F, R, W>(
+const SNIFF_LENGTH: usize = 8000;
+
+pub const ITEM_NAME: &str = "item";
+
+#[derive(Debug, Clone)]
+pub enum Content {
+    Text(String),
+    Binary(Vec<u8>),
+    Directory,
+    Symlink(PathBuf),
+}
+
+impl Content {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Binary(bytes) => bytes,
+            Self::Directory | Self::Symlink(_) => &[],
+        }
+    }
+}
+
+impl PartialEq for Content {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Directory, Self::Directory) => true,
+            (Self::Symlink(target), Self::Symlink(other)) => target == other,
+            (Self::Directory | Self::Symlink(_), _)
+            | (_, Self::Directory | Self::Symlink(_)) => false,
+            _ => self.as_bytes() == other.as_bytes(),
+        }
+    }
+}
+
+/// Content is binary when a NUL byte shows up in its first bytes, like git
+/// decides, or when it isn't valid UTF-8.
+impl From<Vec<u8>> for Content {
+    fn from(bytes: Vec<u8>) -> Self {
+        if bytes.iter().take(SNIFF_LENGTH).any(|b| *b == 0) {
+            return Self::Binary(bytes);
+        }
+
+        match String::from_utf8(bytes) {
+            Ok(text) => Self::Text(text),
+            Err(e) => Self::Binary(e.into_bytes()),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Outcome {
+    Created,
+    Overwritten,
+    Unchanged,
+    Skipped,
+    Conflicted,
+    Removed,
+}
+
+pub fn execute<F, R, W, D>(
     file_finder: F,
     reader: R,
     writer: W,
-) -> Result<(), Error>
+    remover: D,
+) -> Result<Vec<(PathBuf, Outcome)>, Error>
+where
+    F: IntoIterator<Item = PathBuf>,
+    R: Fn(&Path) -> Result<Vec<(PathBuf, Content)>, Error>,
+    W: Fn(&Path, Content, u32) -> Result<Outcome, Error>,
+    D: Fn(&Path) -> Result<(), Error>,
+{
+    let files = render(file_finder, reader)?;
+    write(files, writer, remover)
+}
+
+pub fn render<F, R>(
+    file_finder: F,
+    reader: R,
+) -> Result<Vec<(PathBuf, Content, u32)>, Error>
 where
     F: IntoIterator<Item = PathBuf>,
-    R: Fn(&Path) -> Result<(PathBuf, String), Error>,
-    W: Fn(&Path, String) -> Result<(), Error>,
+    R: Fn(&Path) -> Result<Vec<(PathBuf, Content)>, Error>,
 {
-    file_finder.into_iter().try_for_each(|path| -> Result<(), Error> {
-        let (relative_path, content) = reader(&path)?;
-        writer(&relative_path, content)?;
+    let mut files = Vec::new();
+    for path in file_finder {
         let mode = match path.metadata() {
             Ok(meta) => meta.mode(),
             Err(_) => 0o644,
         };
-        fs::set_permissions(relative_path, fs::Permissions::from_mode(mode))
-            .map_err(|e| Error(e.to_string()))?;
-        Ok(())
-    })?;
+        for (relative_path, content) in reader(&path)? {
+            files.push((relative_path, content, mode));
+        }
+    }
+
+    Ok(files)
+}
+
+pub fn write<W, D>(
+    files: Vec<(PathBuf, Content, u32)>,
+    writer: W,
+    remover: D,
+) -> Result<Vec<(PathBuf, Outcome)>, Error>
+where
+    W: Fn(&Path, Content, u32) -> Result<Outcome, Error>,
+    D: Fn(&Path) -> Result<(), Error>,
+{
+    let mut report = Vec::new();
+    for (relative_path, content, mode) in files {
+        match writer(&relative_path, content, mode) {
+            Ok(outcome) => report.push((relative_path, outcome)),
+            Err(error) => {
+                return match rollback(&report, &remover) {
+                    Ok(()) => Err(error),
+                    Err(e) => Err(Error(format!("{}\n{}", error.0, e.0))),
+                };
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Decides the outcome of every file before writing any of them, so a single
+/// conflict under a failing policy leaves the output untouched. Files whose
+/// status is `Overwritten` are handed to `resolver`, which returns
+/// `Overwritten`, `Skipped` or `Conflicted`.
+pub fn write_resolving_conflicts<S, C, W, D>(
+    files: Vec<(PathBuf, Content, u32)>,
+    status: S,
+    resolver: C,
+    writer: W,
+    remover: D,
+) -> Result<Vec<(PathBuf, Outcome)>, Error>
+where
+    S: Fn(&Path, &Content) -> Outcome,
+    C: Fn(&Path) -> Result<Outcome, Error>,
+    W: Fn(&Path, Content, u32) -> Result<(), Error>,
+    D: Fn(&Path) -> Result<(), Error>,
+{
+    let mut outcomes = HashMap::new();
+    for (path, content, _) in &files {
+        let outcome = match status(path, content) {
+            Outcome::Overwritten => resolver(path)?,
+            outcome => outcome,
+        };
+        outcomes.insert(path.to_owned(), outcome);
+    }
+
+    let conflicts: Vec<_> = files
+        .iter()
+        .filter(|(path, _, _)| outcomes[path] == Outcome::Conflicted)
+        .map(|(path, _, _)| (path.to_owned(), Outcome::Conflicted))
+        .collect();
+    if !conflicts.is_empty() {
+        return Ok(conflicts);
+    }
+
+    write(
+        files,
+        |path, content, mode| {
+            let outcome = outcomes[path];
+            if matches!(outcome, Outcome::Created | Outcome::Overwritten) {
+                writer(path, content, mode)?;
+            }
+            Ok(outcome)
+        },
+        remover,
+    )
+}
+
+/// Reads the file at `path` once, or once per item of a list input when one
+/// of `rules` matches it. Each rule pairs a path matcher with the name of the
+/// list input, and the current item is passed to `reader` as `item`.
+pub fn read_each<M, R>(
+    path: &Path,
+    inputs: &[(String, toml::Value)],
+    rules: &[(M, String)],
+    reader: R,
+) -> Result<Vec<(PathBuf, Content)>, Error>
+where
+    M: Fn(&Path) -> bool,
+    R: Fn(
+        &[(String, toml::Value)],
+    ) -> Result<Option<(PathBuf, Content)>, Error>,
+{
+    let Some((_, each)) = rules.iter().find(|(matches, _)| matches(path))
+    else {
+        return reader(inputs).map(|file| file.into_iter().collect());
+    };
+
+    let files = repeat_inputs(inputs, each)
+        .map_err(|error| {
+            Error(format!(
+                "Unable to repeat file '{}'.\n    {}",
+                path.display(),
+                error.0
+            ))
+        })?
+        .iter()
+        .map(|inputs| reader(inputs))
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match find_duplicate(&files) {
+        Some(duplicate) => Err(Error(format!(
+            "File '{}' renders to the same path '{}' for several items.",
+            path.display(),
+            duplicate.display()
+        ))),
+        None => Ok(files),
+    }
+}
+
+fn repeat_inputs(
+    inputs: &[(String, toml::Value)],
+    each: &str,
+) -> Result<Vec<Vec<(String, toml::Value)>>, Error> {
+    let items = inputs
+        .iter()
+        .rev()
+        .find(|(name, _)| name == each)
+        .and_then(|(_, value)| value.as_array())
+        .ok_or(Error(format!("Input '{each}' is not a list.")))?;
+
+    Ok(items
+        .iter()
+        .map(|item| {
+            let mut result = inputs.to_vec();
+            result.push((ITEM_NAME.to_owned(), item.to_owned()));
+            result
+        })
+        .collect())
+}
+
+fn find_duplicate(files: &[(PathBuf, Content)]) -> Option<&Path> {
+    let mut seen = HashSet::new();
+    files
+        .iter()
+        .map(|(path, _)| path.as_path())
+        .find(|path| !seen.insert(*path))
+}
+
+fn rollback<D>(report: &[(PathBuf, Outcome)], remover: &D) -> Result<(), Error>
+where
+    D: Fn(&Path) -> Result<(), Error>,
+{
+    report
+        .iter()
+        .rev()
+        .filter(|(_, outcome)| *outcome == Outcome::Created)
+        .try_for_each(|(path, _)| remover(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn write_nothing_when_a_file_fails_to_render() {
+        let written = RefCell::new(Vec::new());
+
+        let result = execute(
+            paths(&["a", "b", "c"]),
+            |path| match path.to_str() {
+                Some("b") => Err(Error("broken template".to_owned())),
+                _ => Ok(vec![(path.to_owned(), Content::Text(String::new()))]),
+            },
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(Outcome::Created)
+            },
+            |_| Ok(()),
+        );
+
+        assert_eq!(result, Err(Error("broken template".to_owned())));
+        assert!(written.borrow().is_empty());
+    }
+
+    #[test]
+    fn remove_created_files_when_a_file_fails_to_write() {
+        let removed = RefCell::new(Vec::new());
+
+        let result = execute(
+            paths(&["a", "b", "c", "d"]),
+            |path| Ok(vec![(path.to_owned(), Content::Text(String::new()))]),
+            |path, _, _| match path.to_str() {
+                Some("b") => Ok(Outcome::Overwritten),
+                Some("d") => Err(Error("disk full".to_owned())),
+                _ => Ok(Outcome::Created),
+            },
+            |path| {
+                removed.borrow_mut().push(path.to_owned());
+                Ok(())
+            },
+        );
+
+        assert_eq!(result, Err(Error("disk full".to_owned())));
+        assert_eq!(*removed.borrow(), paths(&["c", "a"]));
+    }
+
+    #[test]
+    fn skip_files_the_reader_leaves_out() {
+        let written = RefCell::new(Vec::new());
+
+        let result = execute(
+            paths(&["a", "b", "c"]),
+            |path| match path.to_str() {
+                Some("b") => Ok(Vec::new()),
+                _ => Ok(vec![(path.to_owned(), Content::Text(String::new()))]),
+            },
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(Outcome::Created)
+            },
+            |_| Ok(()),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                (PathBuf::from("a"), Outcome::Created),
+                (PathBuf::from("c"), Outcome::Created),
+            ])
+        );
+        assert_eq!(*written.borrow(), paths(&["a", "c"]));
+    }
+
+    #[test]
+    fn write_every_file_the_reader_returns() {
+        let written = RefCell::new(Vec::new());
+
+        execute(
+            paths(&["main.rs"]),
+            |_| {
+                Ok(["api/main.rs", "worker/main.rs"]
+                    .iter()
+                    .map(|p| (PathBuf::from(p), Content::Text(String::new())))
+                    .collect())
+            },
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(Outcome::Created)
+            },
+            |_| Ok(()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            *written.borrow(),
+            paths(&["api/main.rs", "worker/main.rs"])
+        );
+    }
+
+    fn files(names: &[&str]) -> Vec<(PathBuf, Content, u32)> {
+        names
+            .iter()
+            .map(|n| (PathBuf::from(n), Content::Text(String::new()), 0o644))
+            .collect()
+    }
+
+    fn existing(path: &Path) -> Outcome {
+        match path.to_str() {
+            Some("new") => Outcome::Created,
+            Some("same") => Outcome::Unchanged,
+            _ => Outcome::Overwritten,
+        }
+    }
+
+    #[test]
+    fn write_nothing_when_any_file_conflicts() {
+        let written = RefCell::new(Vec::new());
+
+        let result = write_resolving_conflicts(
+            files(&["new", "mine", "same", "other"]),
+            |path, _| existing(path),
+            |_| Ok(Outcome::Conflicted),
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(())
+            },
+            |_| Ok(()),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                (PathBuf::from("mine"), Outcome::Conflicted),
+                (PathBuf::from("other"), Outcome::Conflicted),
+            ])
+        );
+        assert!(written.borrow().is_empty());
+    }
+
+    #[test]
+    fn report_skipped_files_and_write_the_rest() {
+        let written = RefCell::new(Vec::new());
+
+        let result = write_resolving_conflicts(
+            files(&["new", "mine", "same"]),
+            |path, _| existing(path),
+            |_| Ok(Outcome::Skipped),
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(())
+            },
+            |_| Ok(()),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                (PathBuf::from("new"), Outcome::Created),
+                (PathBuf::from("mine"), Outcome::Skipped),
+                (PathBuf::from("same"), Outcome::Unchanged),
+            ])
+        );
+        assert_eq!(*written.borrow(), paths(&["new"]));
+    }
+
+    #[test]
+    fn overwrite_files_the_resolver_accepts() {
+        let written = RefCell::new(Vec::new());
+
+        let result = write_resolving_conflicts(
+            files(&["mine", "other"]),
+            |path, _| existing(path),
+            |path| match path.to_str() {
+                Some("mine") => Ok(Outcome::Overwritten),
+                _ => Ok(Outcome::Skipped),
+            },
+            |path, _, _| {
+                written.borrow_mut().push(path.to_owned());
+                Ok(())
+            },
+            |_| Ok(()),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                (PathBuf::from("mine"), Outcome::Overwritten),
+                (PathBuf::from("other"), Outcome::Skipped),
+            ])
+        );
+        assert_eq!(*written.borrow(), paths(&["mine"]));
+    }
+
+    #[test]
+    fn sniff_text_content() {
+        let content = Content::from(b"fn main() {}\n".to_vec());
+
+        assert!(
+            matches!(content, Content::Text(text) if text == "fn main() {}\n")
+        );
+    }
+
+    #[test]
+    fn sniff_binary_content() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+
+        assert!(matches!(Content::from(png), Content::Binary(_)));
+        assert!(matches!(Content::from(vec![0xff, 0xfe]), Content::Binary(_)));
+    }
+
+    #[test]
+    fn compare_directories_and_links_apart_from_files() {
+        assert_eq!(Content::Directory, Content::Directory);
+        assert_ne!(Content::Directory, Content::Text(String::new()));
+        assert_eq!(
+            Content::Symlink(PathBuf::from("docs/index.md")),
+            Content::Symlink(PathBuf::from("docs/index.md"))
+        );
+        assert_ne!(
+            Content::Symlink(PathBuf::from("docs/index.md")),
+            Content::Symlink(PathBuf::from("docs/README.md"))
+        );
+        assert_ne!(
+            Content::Symlink(PathBuf::new()),
+            Content::Text(String::new())
+        );
+        assert_eq!(
+            Content::Text("a".to_owned()),
+            Content::Binary(b"a".to_vec())
+        );
+    }
+
+    fn services() -> Vec<(String, toml::Value)> {
+        let services = ["api", "worker"]
+            .iter()
+            .map(|s| toml::Value::String(s.to_string()))
+            .collect();
+        vec![("services".to_owned(), toml::Value::Array(services))]
+    }
+
+    type Rule = (fn(&Path) -> bool, String);
+
+    fn services_rule() -> Vec<Rule> {
+        vec![(|path| path.starts_with("services"), "services".to_owned())]
+    }
+
+    fn item_path(inputs: &[(String, toml::Value)]) -> PathBuf {
+        let item = inputs
+            .iter()
+            .find(|(name, _)| name == ITEM_NAME)
+            .and_then(|(_, value)| value.as_str())
+            .unwrap_or("none");
+        PathBuf::from(format!("services/{item}/main.rs"))
+    }
+
+    #[test]
+    fn read_once_per_item() {
+        let result = read_each(
+            Path::new("services/main.rs"),
+            &services(),
+            &services_rule(),
+            |inputs| {
+                Ok(Some((item_path(inputs), Content::Text(String::new()))))
+            },
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                (
+                    PathBuf::from("services/api/main.rs"),
+                    Content::Text(String::new())
+                ),
+                (
+                    PathBuf::from("services/worker/main.rs"),
+                    Content::Text(String::new())
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn read_once_when_no_rule_matches() {
+        let result = read_each(
+            Path::new("README.md"),
+            &services(),
+            &services_rule(),
+            |inputs| {
+                Ok(Some((item_path(inputs), Content::Text(String::new()))))
+            },
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![(
+                PathBuf::from("services/none/main.rs"),
+                Content::Text(String::new())
+            )])
+        );
+    }
+
+    #[test]
+    fn fail_when_repeating_over_non_list_input() {
+        let inputs = [("services".to_owned(), toml::Value::from("api"))];
+
+        let result = read_each(
+            Path::new("services/main.rs"),
+            &inputs,
+            &services_rule(),
+            |_| Ok(None),
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(
+                "Unable to repeat file 'services/main.rs'.\n    Input 'services' is not a list."
+                    .to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn fail_when_items_render_to_the_same_path() {
+        let result = read_each(
+            Path::new("services/main.rs"),
+            &services(),
+            &services_rule(),
+            |_| {
+                Ok(Some((
+                    PathBuf::from("services/main.rs"),
+                    Content::Text(String::new()),
+                )))
+            },
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(
+                "File 'services/main.rs' renders to the same path 'services/main.rs' for several items."
+                    .to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn find_duplicate_paths() {
+        let file = |p: &str| (PathBuf::from(p), Content::Text(String::new()));
 
-    Ok(())
+        assert_eq!(find_duplicate(&[file("a"), file("b")]), None);
+        assert_eq!(
+            find_duplicate(&[file("a"), file("b"), file("a")]),
+            Some(Path::new("a"))
+        );
+    }
 }
@@ -0,0 +1,223 @@
+use super::render_skeleton::{Content, Outcome};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+#[derive(thiserror::Error, PartialEq, Debug)]
+#[error("Failed to update skeleton")]
+pub struct Error(pub String);
+
+pub fn execute<R, W, D>(
+    old_files: Vec<(PathBuf, Content)>,
+    new_files: Vec<(PathBuf, Content, u32)>,
+    reader: R,
+    writer: W,
+    remover: D,
+) -> Result<Vec<(PathBuf, Outcome)>, Error>
+where
+    R: Fn(&Path) -> Result<Option<Content>, Error>,
+    W: Fn(&Path, Content, u32) -> Result<(), Error>,
+    D: Fn(&Path) -> Result<(), Error>,
+{
+    let mut old_files: HashMap<PathBuf, Content> =
+        old_files.into_iter().collect();
+    let mut report = Vec::new();
+
+    for (path, new, mode) in new_files {
+        let base = old_files.remove(&path);
+        let current = reader(&path)?;
+        let (content, outcome) = merge(base, current, new);
+        if let Some(content) = content {
+            writer(&path, content, mode)?;
+        }
+        report.push((path, outcome));
+    }
+
+    let mut removed_files: Vec<_> = old_files.into_iter().collect();
+    removed_files.sort_by(|a, b| a.0.cmp(&b.0));
+    for (path, base) in removed_files {
+        match reader(&path)? {
+            Some(current) if current == base => {
+                remover(&path)?;
+                report.push((path, Outcome::Removed));
+            }
+            Some(_) => report.push((path, Outcome::Skipped)),
+            None => {}
+        }
+    }
+
+    Ok(report)
+}
+
+fn merge(
+    base: Option<Content>,
+    current: Option<Content>,
+    new: Content,
+) -> (Option<Content>, Outcome) {
+    match (base, current) {
+        (Some(_), None) => (None, Outcome::Skipped),
+        (None, None) => (Some(new), Outcome::Created),
+        (_, Some(current)) if current == new => (None, Outcome::Unchanged),
+        (Some(base), Some(current)) if base == current => {
+            (Some(new), Outcome::Overwritten)
+        }
+        (Some(base), Some(_)) if base == new => (None, Outcome::Unchanged),
+        (base, Some(current)) => match (base, current, new) {
+            (None, Content::Text(current), Content::Text(new)) => {
+                merge_text("", &current, &new)
+            }
+            (
+                Some(Content::Text(base)),
+                Content::Text(current),
+                Content::Text(new),
+            ) => merge_text(&base, &current, &new),
+            _ => (None, Outcome::Conflicted),
+        },
+    }
+}
+
+fn merge_text(
+    base: &str,
+    current: &str,
+    new: &str,
+) -> (Option<Content>, Outcome) {
+    match diffy::merge(base, current, new) {
+        Ok(merged) => (Some(Content::Text(merged)), Outcome::Overwritten),
+        Err(conflicted) => {
+            (Some(Content::Text(conflicted)), Outcome::Conflicted)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    type Changes = HashMap<PathBuf, Option<Content>>;
+
+    fn text(content: &str) -> Content {
+        Content::Text(content.to_owned())
+    }
+
+    fn update(
+        old: &[(&str, &str)],
+        new: &[(&str, &str)],
+        current: &[(&str, &str)],
+    ) -> (Vec<(PathBuf, Outcome)>, Changes) {
+        let current: HashMap<PathBuf, Content> =
+            current.iter().map(|(p, c)| (PathBuf::from(p), text(c))).collect();
+        let changes = RefCell::new(HashMap::new());
+
+        let report = execute(
+            old.iter().map(|(p, c)| (PathBuf::from(p), text(c))).collect(),
+            new.iter()
+                .map(|(p, c)| (PathBuf::from(p), text(c), 0o644))
+                .collect(),
+            |path| Ok(current.get(path).cloned()),
+            |path, content, _| {
+                changes.borrow_mut().insert(path.to_owned(), Some(content));
+                Ok(())
+            },
+            |path| {
+                changes.borrow_mut().insert(path.to_owned(), None);
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        (report, changes.into_inner())
+    }
+
+    #[test]
+    fn take_new_content_when_file_was_not_modified() {
+        let (report, changes) =
+            update(&[("a", "old\n")], &[("a", "new\n")], &[("a", "old\n")]);
+
+        assert_eq!(report, vec![(PathBuf::from("a"), Outcome::Overwritten)]);
+        assert_eq!(changes[Path::new("a")], Some(text("new\n")));
+    }
+
+    #[test]
+    fn keep_local_changes_when_skeleton_did_not_change() {
+        let (report, changes) =
+            update(&[("a", "old\n")], &[("a", "old\n")], &[("a", "mine\n")]);
+
+        assert_eq!(report, vec![(PathBuf::from("a"), Outcome::Unchanged)]);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn merge_local_and_skeleton_changes() {
+        let (report, changes) = update(
+            &[("a", "one\ntwo\nthree\n")],
+            &[("a", "one\ntwo\nTHREE\n")],
+            &[("a", "ONE\ntwo\nthree\n")],
+        );
+
+        assert_eq!(report, vec![(PathBuf::from("a"), Outcome::Overwritten)]);
+        assert_eq!(changes[Path::new("a")], Some(text("ONE\ntwo\nTHREE\n")));
+    }
+
+    #[test]
+    fn write_conflict_markers_when_changes_overlap() {
+        let (report, changes) =
+            update(&[("a", "old\n")], &[("a", "new\n")], &[("a", "mine\n")]);
+
+        assert_eq!(report, vec![(PathBuf::from("a"), Outcome::Conflicted)]);
+        let Some(Content::Text(content)) = changes[Path::new("a")].clone()
+        else {
+            panic!("conflict markers were not written");
+        };
+        assert!(content.contains("<<<<<<<"));
+        assert!(content.contains("mine\n"));
+        assert!(content.contains("new\n"));
+    }
+
+    #[test]
+    fn create_new_files_and_skip_deleted_ones() {
+        let (report, changes) =
+            update(&[("a", "old\n")], &[("a", "new\n"), ("b", "new\n")], &[]);
+
+        assert_eq!(
+            report,
+            vec![
+                (PathBuf::from("a"), Outcome::Skipped),
+                (PathBuf::from("b"), Outcome::Created),
+            ]
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[Path::new("b")], Some(text("new\n")));
+    }
+
+    #[test]
+    fn remove_files_dropped_from_skeleton_unless_modified() {
+        let (report, changes) = update(
+            &[("a", "old\n"), ("b", "old\n")],
+            &[],
+            &[("a", "old\n"), ("b", "mine\n")],
+        );
+
+        assert_eq!(
+            report,
+            vec![
+                (PathBuf::from("a"), Outcome::Removed),
+                (PathBuf::from("b"), Outcome::Skipped),
+            ]
+        );
+        assert_eq!(changes[Path::new("a")], None);
+        assert_eq!(changes.len(), 1);
+    }
+
+    #[test]
+    fn keep_local_binary_file_when_both_sides_changed() {
+        let base = Content::Binary(vec![0, 1]);
+        let current = Content::Binary(vec![0, 2]);
+        let new = Content::Binary(vec![0, 3]);
+
+        let result = merge(Some(base), Some(current), new);
+
+        assert_eq!(result, (None, Outcome::Conflicted));
+    }
+}
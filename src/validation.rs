@@ -1,4 +1,5 @@
-use crate::config::Input;
+use crate::config::{Input, InputType, value_to_string};
+use crate::renderer;
 use std::collections::HashMap;
 
 #[derive(thiserror::Error, PartialEq, Debug)]
@@ -8,45 +9,171 @@ pub struct Error(pub Vec<ErrorType>);
 #[derive(PartialEq, Debug)]
 pub enum ErrorType {
     MissingInput(String),
+    UnknownInput(String, Option<String>),
     InvalidOption(String, String, Vec<String>),
+    InvalidType(String, String, InputType),
+    PatternMismatch(String, String, String),
+    TooShort(String, String, usize),
+    TooLong(String, String, usize),
+    TooSmall(String, String, f64),
+    TooLarge(String, String, f64),
+    InvalidCondition(String, String),
 }
 
+impl ErrorType {
+    pub fn input_name(&self) -> &str {
+        match self {
+            Self::MissingInput(name)
+            | Self::UnknownInput(name, _)
+            | Self::InvalidOption(name, ..)
+            | Self::InvalidType(name, ..)
+            | Self::PatternMismatch(name, ..)
+            | Self::TooShort(name, ..)
+            | Self::TooLong(name, ..)
+            | Self::TooSmall(name, ..)
+            | Self::TooLarge(name, ..)
+            | Self::InvalidCondition(name, _) => name,
+        }
+    }
+}
+
+const ENV_PREFIX: &str = "SKELLY_INPUT_";
+
 pub fn validate_inputs(
-    user_inputs: &[(String, String)],
+    user_inputs: &[(String, toml::Value)],
+    config_inputs: &[Input],
+) -> Result<Vec<(String, toml::Value)>, Error> {
+    validate_inputs_with_env(user_inputs, config_inputs, |key| {
+        std::env::var(key).ok()
+    })
+}
+
+fn validate_inputs_with_env<E>(
+    user_inputs: &[(String, toml::Value)],
     config_inputs: &[Input],
-) -> Result<Vec<(String, String)>, Error> {
+    env: E,
+) -> Result<Vec<(String, toml::Value)>, Error>
+where
+    E: Fn(&str) -> Option<String>,
+{
     let input_map = create_input_map(config_inputs);
-    let mut inputs = create_inputs_with_defaults(config_inputs);
+    let mut inputs = create_inputs_with_defaults(config_inputs, env);
     let mut errors = Vec::new();
 
-    fill_with_valid_inputs(user_inputs, &input_map, &mut inputs, &mut errors);
-    check_for_missing_inputs(&inputs, &input_map, &mut errors);
+    let user_inputs = collapse_inputs(user_inputs);
+    fill_with_valid_inputs(&user_inputs, &input_map, &mut inputs, &mut errors);
+    let typed_inputs = convert_inputs(&inputs, &input_map, &mut errors);
+    check_for_missing_inputs(
+        &inputs,
+        &typed_inputs,
+        config_inputs,
+        &mut errors,
+    );
+
+    if errors.is_empty() { Ok(typed_inputs) } else { Err(Error(errors)) }
+}
 
-    if errors.is_empty() {
-        Ok(inputs.into_iter().collect())
-    } else {
-        Err(Error(errors))
-    }
+pub fn check_input(
+    name: &str,
+    user_inputs: &[(String, toml::Value)],
+    config_inputs: &[Input],
+) -> Vec<ErrorType> {
+    let Err(error) = validate_inputs(user_inputs, config_inputs) else {
+        return Vec::new();
+    };
+
+    error
+        .0
+        .into_iter()
+        .filter(|e| e.input_name() == name)
+        .filter(|e| !matches!(e, ErrorType::MissingInput(_)))
+        .collect()
+}
+
+pub fn find_unknown_inputs(
+    user_inputs: &[(String, toml::Value)],
+    config_inputs: &[Input],
+) -> Vec<ErrorType> {
+    let mut names: Vec<&String> = user_inputs
+        .iter()
+        .map(|ui| &ui.0)
+        .filter(|name| !config_inputs.iter().any(|ci| &ci.name == *name))
+        .collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            ErrorType::UnknownInput(
+                name.to_owned(),
+                closest_input_name(name, config_inputs),
+            )
+        })
+        .collect()
+}
+
+fn closest_input_name(name: &str, config_inputs: &[Input]) -> Option<String> {
+    config_inputs
+        .iter()
+        .map(|ci| (strsim::jaro(name, &ci.name), &ci.name))
+        .filter(|(confidence, _)| *confidence > 0.7)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, name)| name.to_owned())
 }
 
 fn create_input_map(inputs: &[Input]) -> HashMap<String, Input> {
     inputs.iter().map(|i| (i.name.to_owned(), i.to_owned())).collect()
 }
 
-fn create_inputs_with_defaults(inputs: &[Input]) -> HashMap<String, String> {
+fn create_inputs_with_defaults<E>(
+    inputs: &[Input],
+    env: E,
+) -> HashMap<String, toml::Value>
+where
+    E: Fn(&str) -> Option<String>,
+{
     let mut result = HashMap::new();
     for input in inputs {
-        if let Some(value) = &input.default {
-            result.insert(input.name.to_owned(), value.to_owned());
+        let env_value = input
+            .env
+            .as_deref()
+            .and_then(&env)
+            .or_else(|| env(&env_name(&input.name)));
+        if let Some(value) = env_value.or(input.default.to_owned()) {
+            result.insert(input.name.to_owned(), toml::Value::String(value));
+        }
+    }
+    result
+}
+
+fn env_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' }
+        })
+        .collect();
+    format!("{ENV_PREFIX}{name}")
+}
+
+fn collapse_inputs(
+    user_inputs: &[(String, toml::Value)],
+) -> Vec<(String, toml::Value)> {
+    let mut result: Vec<(String, toml::Value)> = Vec::new();
+    for (key, value) in user_inputs {
+        match result.iter_mut().find(|r| &r.0 == key) {
+            Some(existing) => existing.1 = value.to_owned(),
+            None => result.push((key.to_owned(), value.to_owned())),
         }
     }
     result
 }
 
 fn fill_with_valid_inputs(
-    user_inputs: &[(String, String)],
+    user_inputs: &[(String, toml::Value)],
     input_map: &HashMap<String, Input>,
-    inputs: &mut HashMap<String, String>,
+    inputs: &mut HashMap<String, toml::Value>,
     errors: &mut Vec<ErrorType>,
 ) {
     for ui in user_inputs {
@@ -54,12 +181,13 @@ fn fill_with_valid_inputs(
             continue;
         }
         let options = fetch_options(input_map, &ui.0);
-        if has_option(&ui.1, &options) {
+        let value = value_to_string(&ui.1);
+        if has_option(&value, &options) {
             inputs.insert(ui.0.to_owned(), ui.1.to_owned());
         } else {
             errors.push(ErrorType::InvalidOption(
                 ui.0.to_owned(),
-                ui.1.to_owned(),
+                value,
                 options,
             ));
         }
@@ -75,15 +203,146 @@ fn has_option(option: &String, options: &[String]) -> bool {
 }
 
 fn check_for_missing_inputs(
-    inputs: &HashMap<String, String>,
+    inputs: &HashMap<String, toml::Value>,
+    typed_inputs: &[(String, toml::Value)],
+    config_inputs: &[Input],
+    errors: &mut Vec<ErrorType>,
+) {
+    let mut unset = Vec::new();
+    for input in config_inputs {
+        if inputs.contains_key(&input.name) {
+            continue;
+        }
+        match is_required(input, typed_inputs, &unset) {
+            Ok(true) => {
+                errors.push(ErrorType::MissingInput(input.name.to_owned()))
+            }
+            Ok(false) => {}
+            Err(message) => errors.push(ErrorType::InvalidCondition(
+                input.name.to_owned(),
+                message,
+            )),
+        }
+        unset.push(input.name.as_str());
+    }
+}
+
+fn is_required(
+    input: &Input,
+    typed_inputs: &[(String, toml::Value)],
+    unset: &[&str],
+) -> Result<bool, String> {
+    let Some(condition) = &input.when else {
+        return Ok(true);
+    };
+    match renderer::evaluate(condition, typed_inputs) {
+        Ok(required) => Ok(required),
+        Err(_) if mentions_any(condition, unset) => Ok(false),
+        Err(e) => Err(e.0),
+    }
+}
+
+fn mentions_any(condition: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| {
+        regex::Regex::new(&format!(r"\b{}\b", regex::escape(name)))
+            .is_ok_and(|r| r.is_match(condition))
+    })
+}
+
+fn convert_inputs(
+    inputs: &HashMap<String, toml::Value>,
     input_map: &HashMap<String, Input>,
     errors: &mut Vec<ErrorType>,
+) -> Vec<(String, toml::Value)> {
+    let mut result = Vec::new();
+    for (name, value) in inputs {
+        let Some(input) = input_map.get(name) else {
+            continue;
+        };
+        let kind = input.kind;
+        let raw_value = value_to_string(value);
+        match kind.convert(value) {
+            Some(typed) => {
+                check_constraints(input, &raw_value, &typed, errors);
+                result.push((name.to_owned(), typed));
+            }
+            None => errors.push(ErrorType::InvalidType(
+                name.to_owned(),
+                raw_value,
+                kind,
+            )),
+        }
+    }
+    result
+}
+
+fn check_constraints(
+    input: &Input,
+    value: &str,
+    typed: &toml::Value,
+    errors: &mut Vec<ErrorType>,
 ) {
-    for im in input_map.values() {
-        if !inputs.contains_key(&im.name) {
-            errors.push(ErrorType::MissingInput(im.name.to_owned()));
+    let name = input.name.to_owned();
+
+    if let Some(pattern) = &input.pattern {
+        let matches = regex::Regex::new(pattern)
+            .map(|r| r.is_match(value))
+            .unwrap_or(false);
+        if !matches {
+            errors.push(ErrorType::PatternMismatch(
+                name.to_owned(),
+                value.to_owned(),
+                pattern.to_owned(),
+            ));
+        }
+    }
+
+    if let Some(length) = value_length(typed) {
+        if let Some(min_length) = input.min_length.filter(|m| length < *m) {
+            errors.push(ErrorType::TooShort(
+                name.to_owned(),
+                value.to_owned(),
+                min_length,
+            ));
+        }
+        if let Some(max_length) = input.max_length.filter(|m| length > *m) {
+            errors.push(ErrorType::TooLong(
+                name.to_owned(),
+                value.to_owned(),
+                max_length,
+            ));
         }
     }
+
+    if let Some(number) = value_number(typed) {
+        if let Some(min) = input.min.filter(|m| number < *m) {
+            errors.push(ErrorType::TooSmall(
+                name.to_owned(),
+                value.to_owned(),
+                min,
+            ));
+        }
+        if let Some(max) = input.max.filter(|m| number > *m) {
+            errors.push(ErrorType::TooLarge(name, value.to_owned(), max));
+        }
+    }
+}
+
+fn value_length(value: &toml::Value) -> Option<usize> {
+    match value {
+        toml::Value::String(v) => Some(v.chars().count()),
+        toml::Value::Array(v) => Some(v.len()),
+        toml::Value::Table(v) => Some(v.len()),
+        _ => None,
+    }
+}
+
+fn value_number(value: &toml::Value) -> Option<f64> {
+    match value {
+        toml::Value::Integer(v) => Some(*v as f64),
+        toml::Value::Float(v) => Some(*v),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -92,11 +351,16 @@ mod tests {
 
     use super::*;
 
+    fn string(value: &str) -> toml::Value {
+        toml::Value::String(value.to_owned())
+    }
+
     fn make_config_inputs() -> Vec<Input> {
         vec![Input {
             name: "test".to_owned(),
             default: Some("ok".to_owned()),
             options: None,
+            ..Default::default()
         }]
     }
 
@@ -106,7 +370,7 @@ mod tests {
 
         let result = validate_inputs(&[], &input_map);
 
-        assert_eq!(result, Ok(vec![("test".to_owned(), "ok".to_owned())]));
+        assert_eq!(result, Ok(vec![("test".to_owned(), string("ok"))]));
     }
 
     #[test]
@@ -114,11 +378,11 @@ mod tests {
         let input_map = make_config_inputs();
 
         let result = validate_inputs(
-            &[("test".to_owned(), "updated".to_owned())],
+            &[("test".to_owned(), string("updated"))],
             &input_map,
         );
 
-        assert_eq!(result, Ok(vec![("test".to_owned(), "updated".to_owned())]));
+        assert_eq!(result, Ok(vec![("test".to_owned(), string("updated"))]));
     }
 
     #[test]
@@ -126,11 +390,11 @@ mod tests {
         let input_map = make_config_inputs();
 
         let result = validate_inputs(
-            &[("unknown".to_owned(), "ignore".to_owned())],
+            &[("unknown".to_owned(), string("ignore"))],
             &input_map,
         );
 
-        assert_eq!(result, Ok(vec![("test".to_owned(), "ok".to_owned())]));
+        assert_eq!(result, Ok(vec![("test".to_owned(), string("ok"))]));
     }
 
     #[test]
@@ -139,14 +403,15 @@ mod tests {
             name: "test".to_owned(),
             default: None,
             options: Some(Vec::new()),
+            ..Default::default()
         }];
 
         let result = validate_inputs(
-            &[("test".to_owned(), "invalid".to_owned())],
+            &[("test".to_owned(), string("invalid"))],
             &config_inputs,
         );
 
-        assert_eq!(result, Ok(vec![("test".to_owned(), "invalid".to_owned())]),);
+        assert_eq!(result, Ok(vec![("test".to_owned(), string("invalid"))]),);
     }
 
     #[test]
@@ -155,6 +420,7 @@ mod tests {
             name: "test".to_owned(),
             default: None,
             options: None,
+            ..Default::default()
         }];
 
         let got = validate_inputs(&[], &config_inputs);
@@ -169,10 +435,11 @@ mod tests {
             name: "test".to_owned(),
             default: None,
             options: Some(vec!["ok".to_owned(), "fail".to_owned()]),
+            ..Default::default()
         }];
 
         let result = validate_inputs(
-            &[("test".to_owned(), "invalid".to_owned())],
+            &[("test".to_owned(), string("invalid"))],
             &config_inputs,
         );
 
@@ -188,4 +455,383 @@ mod tests {
             ])),
         );
     }
+
+    #[test]
+    fn use_only_the_last_value_of_repeated_inputs() {
+        let config_inputs = vec![Input {
+            name: "test".to_owned(),
+            default: None,
+            options: Some(vec!["ok".to_owned(), "fail".to_owned()]),
+            ..Default::default()
+        }];
+
+        let result = validate_inputs(
+            &[
+                ("test".to_owned(), string("invalid")),
+                ("test".to_owned(), string("ok")),
+            ],
+            &config_inputs,
+        );
+
+        assert_eq!(result, Ok(vec![("test".to_owned(), string("ok"))]));
+    }
+
+    #[test]
+    fn convert_inputs_to_their_types() {
+        let config_inputs = vec![
+            Input {
+                name: "use_docker".to_owned(),
+                kind: InputType::Bool,
+                default: Some("false".to_owned()),
+                ..Default::default()
+            },
+            Input {
+                name: "port".to_owned(),
+                kind: InputType::Integer,
+                ..Default::default()
+            },
+        ];
+
+        let mut result = validate_inputs(
+            &[("port".to_owned(), string("8080"))],
+            &config_inputs,
+        )
+        .unwrap();
+        result.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            result,
+            vec![
+                ("port".to_owned(), toml::Value::Integer(8080)),
+                ("use_docker".to_owned(), toml::Value::Boolean(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn return_error_when_input_has_invalid_type() {
+        let config_inputs = vec![Input {
+            name: "port".to_owned(),
+            kind: InputType::Integer,
+            ..Default::default()
+        }];
+
+        let result = validate_inputs(
+            &[("port".to_owned(), string("http"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(vec![ErrorType::InvalidType(
+                "port".to_owned(),
+                "http".to_owned(),
+                InputType::Integer,
+            )])),
+        );
+    }
+
+    #[test]
+    fn return_all_constraint_errors_together() {
+        let config_inputs = vec![
+            Input {
+                name: "crate_name".to_owned(),
+                pattern: Some("^[a-z][a-z0-9_]*$".to_owned()),
+                max_length: Some(8),
+                ..Default::default()
+            },
+            Input {
+                name: "workers".to_owned(),
+                kind: InputType::Integer,
+                min: Some(1.0),
+                ..Default::default()
+            },
+        ];
+
+        let result = validate_inputs(
+            &[
+                ("crate_name".to_owned(), string("My Crate!")),
+                ("workers".to_owned(), string("0")),
+            ],
+            &config_inputs,
+        );
+
+        let mut errors = result.unwrap_err().0;
+        errors.sort_by_key(|e| format!("{e:?}"));
+        assert_eq!(
+            errors,
+            vec![
+                ErrorType::PatternMismatch(
+                    "crate_name".to_owned(),
+                    "My Crate!".to_owned(),
+                    "^[a-z][a-z0-9_]*$".to_owned(),
+                ),
+                ErrorType::TooLong(
+                    "crate_name".to_owned(),
+                    "My Crate!".to_owned(),
+                    8,
+                ),
+                ErrorType::TooSmall("workers".to_owned(), "0".to_owned(), 1.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn check_length_of_lists() {
+        let config_inputs = vec![Input {
+            name: "services".to_owned(),
+            kind: InputType::List,
+            min_length: Some(2),
+            ..Default::default()
+        }];
+
+        let result = validate_inputs(
+            &[("services".to_owned(), string("api"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(vec![ErrorType::TooShort(
+                "services".to_owned(),
+                "api".to_owned(),
+                2,
+            )])),
+        );
+    }
+
+    #[test]
+    fn accept_values_within_constraints() {
+        let config_inputs = vec![Input {
+            name: "port".to_owned(),
+            kind: InputType::Integer,
+            min: Some(1.0),
+            max: Some(65535.0),
+            ..Default::default()
+        }];
+
+        let result = validate_inputs(
+            &[("port".to_owned(), string("8080"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![("port".to_owned(), toml::Value::Integer(8080))])
+        );
+    }
+
+    fn make_conditional_inputs() -> Vec<Input> {
+        vec![
+            Input {
+                name: "use_docker".to_owned(),
+                kind: InputType::Bool,
+                default: Some("false".to_owned()),
+                ..Default::default()
+            },
+            Input {
+                name: "docker_registry".to_owned(),
+                when: Some("use_docker".to_owned()),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn skip_input_when_condition_is_false() {
+        let config_inputs = make_conditional_inputs();
+
+        let result = validate_inputs(&[], &config_inputs);
+
+        assert_eq!(
+            result,
+            Ok(vec![("use_docker".to_owned(), toml::Value::Boolean(false))])
+        );
+    }
+
+    #[test]
+    fn require_input_when_condition_is_true() {
+        let config_inputs = make_conditional_inputs();
+
+        let result = validate_inputs(
+            &[("use_docker".to_owned(), string("true"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(vec![ErrorType::MissingInput(
+                "docker_registry".to_owned()
+            )])),
+        );
+    }
+
+    #[test]
+    fn skip_input_whose_condition_uses_a_skipped_input() {
+        let mut config_inputs = make_conditional_inputs();
+        config_inputs.push(Input {
+            name: "registry_user".to_owned(),
+            when: Some("docker_registry != 'docker.io'".to_owned()),
+            ..Default::default()
+        });
+
+        let result = validate_inputs(
+            &[("use_docker".to_owned(), string("false"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![("use_docker".to_owned(), toml::Value::Boolean(false))])
+        );
+    }
+
+    #[test]
+    fn evaluate_conditions_in_declaration_order() {
+        let mut config_inputs = make_conditional_inputs();
+        config_inputs.push(Input {
+            name: "registry_user".to_owned(),
+            when: Some("docker_registry != 'docker.io'".to_owned()),
+            ..Default::default()
+        });
+
+        let result = validate_inputs(
+            &[
+                ("use_docker".to_owned(), string("true")),
+                ("docker_registry".to_owned(), string("ghcr.io")),
+            ],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            Err(Error(vec![ErrorType::MissingInput(
+                "registry_user".to_owned()
+            )])),
+        );
+    }
+
+    fn make_env(
+        vars: &[(&str, &str)],
+    ) -> impl Fn(&str) -> Option<String> + use<> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    #[test]
+    fn return_env_value_over_default() {
+        let config_inputs = vec![Input {
+            name: "project_name".to_owned(),
+            default: Some("default".to_owned()),
+            env: Some("CI_PROJECT_NAME".to_owned()),
+            ..Default::default()
+        }];
+
+        let result = validate_inputs_with_env(
+            &[],
+            &config_inputs,
+            make_env(&[("CI_PROJECT_NAME", "from-ci")]),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![("project_name".to_owned(), string("from-ci"))])
+        );
+    }
+
+    #[test]
+    fn return_prefixed_env_value() {
+        let config_inputs =
+            vec![Input { name: "build-tool".to_owned(), ..Default::default() }];
+
+        let result = validate_inputs_with_env(
+            &[],
+            &config_inputs,
+            make_env(&[("SKELLY_INPUT_BUILD_TOOL", "cargo")]),
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![("build-tool".to_owned(), string("cargo"))])
+        );
+    }
+
+    #[test]
+    fn return_user_input_over_env_value() {
+        let config_inputs = vec![Input {
+            name: "name".to_owned(),
+            env: Some("NAME".to_owned()),
+            ..Default::default()
+        }];
+
+        let result = validate_inputs_with_env(
+            &[("name".to_owned(), string("cli"))],
+            &config_inputs,
+            make_env(&[("NAME", "env"), ("SKELLY_INPUT_NAME", "prefixed")]),
+        );
+
+        assert_eq!(result, Ok(vec![("name".to_owned(), string("cli"))]));
+    }
+
+    #[test]
+    fn find_unknown_inputs_with_suggestions() {
+        let config_inputs = vec![
+            Input { name: "build_tool".to_owned(), ..Default::default() },
+            Input { name: "target_path".to_owned(), ..Default::default() },
+        ];
+
+        let result = find_unknown_inputs(
+            &[
+                ("buid_tool".to_owned(), string("rustc")),
+                ("target_path".to_owned(), string("dist")),
+                ("zzz".to_owned(), string("value")),
+            ],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            result,
+            vec![
+                ErrorType::UnknownInput(
+                    "buid_tool".to_owned(),
+                    Some("build_tool".to_owned())
+                ),
+                ErrorType::UnknownInput("zzz".to_owned(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_only_the_given_input() {
+        let config_inputs = vec![
+            Input {
+                name: "name".to_owned(),
+                pattern: Some("^[a-z]+$".to_owned()),
+                ..Default::default()
+            },
+            Input { name: "other".to_owned(), ..Default::default() },
+        ];
+
+        let invalid = check_input(
+            "name",
+            &[("name".to_owned(), string("Bad Name"))],
+            &config_inputs,
+        );
+        let valid = check_input(
+            "name",
+            &[("name".to_owned(), string("good"))],
+            &config_inputs,
+        );
+
+        assert_eq!(
+            invalid,
+            vec![ErrorType::PatternMismatch(
+                "name".to_owned(),
+                "Bad Name".to_owned(),
+                "^[a-z]+$".to_owned()
+            )]
+        );
+        assert_eq!(valid, Vec::new());
+    }
 }